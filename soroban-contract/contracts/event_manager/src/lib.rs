@@ -1,31 +1,160 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, String, Symbol, Vec,
+    contract, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal,
+    Map, String, Symbol, Vec,
 };
 
-// Error handling
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub enum Error {
-    AlreadyInitialized = 1,
-    EventNotFound = 2,
-    EventAlreadyCanceled = 3,
-    CannotSellMoreTickets = 4,
-    InvalidStartDate = 5,
-    InvalidEndDate = 6,
-    NegativeTicketPrice = 7,
-    InvalidTicketCount = 8,
-    CounterOverflow = 9,
-    FactoryNotInitialized = 10,
-}
-
 // Storage keys
 #[contracttype]
 pub enum DataKey {
     Event(u32),
     EventCounter,
     TicketFactory,
+    /// Ticket revenue held in escrow for an event until it ends
+    EventBalance(u32),
+    /// Platform fees held in escrow for an event until it settles
+    EventFees(u32),
+    /// Amount actually paid for a ticket: (event_id, token_id) -> amount
+    TicketPayment(u32, u128),
+    /// Refund claimed flag: (event_id, token_id) -> bool
+    RefundClaimed(u32, u128),
+    /// Ticket tier: (event_id, tier_id) -> TicketTier
+    TicketTier(u32, u32),
+    /// Number of tiers created for an event
+    TierCount(u32),
+    /// Events created by an organizer: (organizer, index) -> event_id
+    OrganizerEvent(Address, u32),
+    /// Number of events created by an organizer
+    OrganizerEventCount(Address),
+    /// Events of a given type: (event_type, index) -> event_id
+    TypeEvent(String, u32),
+    /// Number of events of a given type
+    TypeEventCount(String),
+    /// Canceled or archived ended events: (status, index) -> event_id
+    StatusEvent(EventStatus, u32),
+    /// Number of events in a status index
+    StatusEventCount(EventStatus),
+    /// Live events dated on a day, sorted by date: (index, day) -> (date, event_id)
+    DateBucket(DateIndex, u64),
+    /// Days with live events in a date index, in order
+    DateBucketDays(DateIndex),
+    /// Purchase timestamp of a ticket: (event_id, token_id) -> timestamp
+    TicketPurchasedAt(u32, u128),
+    /// Refund window length applied when an event is rescheduled
+    RescheduleRefundWindow(u32),
+    /// Refund window opened by the latest reschedule of an event
+    RescheduleWindow(u32),
+    /// Staff roles granted for an event
+    Role(u32, Address, Role),
+    /// ed25519 public key a ticket holder signs check-in payloads with
+    CheckinKey(Address),
+    /// Check-in nonces already used by a holder
+    CheckinNonce(Address, u64),
+    /// Platform admin allowed to configure fees
+    Admin,
+    /// Platform fee applied to newly created events
+    PlatformFee,
+    /// Platform fee fixed for an event when it was created
+    EventFee(u32),
+    /// Revenue split table of an event
+    RevenueSplits(u32),
+    /// Set once an event sells its first ticket, after which splits can't change
+    SplitsLocked(u32),
+    /// Revenue already paid out to a payee: (event_id, payee) -> i128
+    SplitPaid(u32, Address),
+    /// Revenue already paid out to all payees of an event
+    SplitPaidTotal(u32),
+    /// Whether any of an event's revenue has been paid out
+    RevenueWithdrawn(u32),
+    /// Resale royalty and price cap of an event
+    ResalePolicy(u32),
+    /// Resale listing: (event_id, token_id) -> Listing
+    Listing(u32, u128),
+    /// Seconds before the start during which ticket transfers are frozen
+    TransferLockWindow(u32),
+    /// Waitlist queue bounds and size of an event
+    Waitlist(u32),
+    /// Waitlist entry: (event_id, queue index) -> WaitlistEntry
+    WaitlistEntry(u32, u32),
+    /// Queue index of a waitlisted user: (event_id, user) -> u32
+    WaitlistIndex(u32, Address),
+    /// Presale phase of an event
+    Presale(u32),
+    /// Time-based general admission price schedule of an event
+    PriceSchedule(u32),
+    /// Demand-based general admission pricing curve of an event
+    BondingCurve(u32),
+    /// Dutch auction configuration of an event
+    DutchAuction(u32),
+    /// Bid count and clearing price of an event's Dutch auction
+    AuctionState(u32),
+    /// Amount escrowed by a bidder in an event's Dutch auction
+    AuctionBid(u32, Address),
+    /// Lottery configuration of an event
+    Lottery(u32),
+    /// Entry count and draw result of an event's lottery
+    LotteryState(u32),
+    /// Lottery entrant by entry index
+    LotteryEntry(u32, u32),
+    /// Deposit escrowed by a lottery entrant
+    LotteryDeposit(u32, Address),
+    /// Whether a lottery entrant was drawn
+    LotteryWinner(u32, Address),
+    /// Promo code of an event, keyed by the SHA-256 hash of the code
+    PromoCode(u32, BytesN<32>),
+}
+
+/// Per-wallet ticket limit for newly deployed ticket contracts
+const DEFAULT_MAX_TICKETS_PER_WALLET: u32 = 1;
+
+/// Collection symbol of newly deployed ticket contracts
+const TICKET_SYMBOL: &str = "TICKET";
+
+/// Default opt-out refund window after a reschedule (~7 days)
+const DEFAULT_RESCHEDULE_REFUND_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// Maximum platform fee in basis points (10%)
+const MAX_FEE_BPS: u32 = 1_000;
+
+/// Denominator for basis point calculations
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// How long a waitlisted user has to claim a freed ticket (~1 day)
+const WAITLIST_CLAIM_WINDOW: u64 = 24 * 60 * 60;
+
+/// Maximum per-ticket growth of an exponential bonding curve in basis points (10%)
+const MAX_CURVE_GROWTH_BPS: u32 = 1_000;
+
+/// Fixed-point scale used when compounding exponential curve growth
+const CURVE_SCALE: i128 = 1_000_000_000;
+
+/// Maximum number of events returned by a single listing call
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Width of a date index bucket (one day)
+const DATE_BUCKET_SECONDS: u64 = 24 * 60 * 60;
+
+/// Maximum number of ended events moved out of the date indexes per index update
+const ARCHIVE_BATCH: u32 = 10;
+
+// Date an event is indexed by in the live event indexes
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DateIndex {
+    Start,
+    End,
+}
+
+// Lifecycle status of an event, derived from its dates and cancellation flag
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventStatus {
+    Upcoming,
+    Active,
+    Ended,
+    Canceled,
 }
 
 // Event structure
@@ -46,27 +175,290 @@ pub struct Event {
     pub payment_token: Address,
 }
 
+// Ticket tier structure. Tier 0 is the event's default tier and mirrors
+// `ticket_price` and `total_tickets` on the `Event`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketTier {
+    pub id: u32,
+    pub name: String,
+    pub price: i128,
+    pub total_supply: u128,
+    pub sold: u128,
+    pub sale_start: Option<u64>,
+    pub sale_end: Option<u64>,
+}
+
+// Opt-out refund window opened by a reschedule. Tickets purchased up to
+// `rescheduled_at` can be refunded until `refund_deadline`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RescheduleWindow {
+    pub rescheduled_at: u64,
+    pub refund_deadline: u64,
+}
+
+// Transfer policy of an event's ticket contract. Mirrors `TransferPolicy` in the
+// ticket NFT contract.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    /// Holders can transfer tickets freely
+    Free,
+    /// Tickets can never leave the wallet they were minted to
+    Soulbound,
+    /// Each transfer must be approved by the organizer
+    OrganizerApproved,
+}
+
+// Staff roles an organizer can grant for an event. The organizer implicitly holds all of them.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Can edit and reschedule the event. Canceling stays with the organizer.
+    CoOrganizer,
+    /// Can check in tickets
+    Scanner,
+    /// Can withdraw event revenue
+    Finance,
+}
+
+// Check-in payload signed offline by a ticket holder. The signature covers the
+// XDR encoding of `(event_manager, event_id, token_id, expires_at, nonce)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedCheckin {
+    pub token_id: u128,
+    pub expires_at: u64,
+    pub nonce: u64,
+    pub signature: BytesN<64>,
+}
+
+// Platform fee taken from every ticket sale and sent to the treasury. The fee per
+// ticket is `price * fee_bps / 10_000 + flat_fee`, never more than the ticket price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFee {
+    pub fee_bps: u32,
+    /// Flat fee per ticket, in units of the event's payment token
+    pub flat_fee: i128,
+    pub treasury: Address,
+}
+
+// Share of an event's revenue paid to a payee, in basis points of the gross
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueSplit {
+    pub payee: Address,
+    pub bps: u32,
+}
+
+// Secondary market rules set by the organizer. The royalty is paid to the organizer
+// on every resale, and `max_price_bps` caps the resale price relative to face value.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResalePolicy {
+    pub royalty_bps: u32,
+    pub max_price_bps: Option<u32>,
+}
+
+// Ticket offered for resale. The event manager is approved to transfer the ticket
+// while the listing is active.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub seller: Address,
+    pub price: i128,
+}
+
+// Queue bounds of an event's waitlist. Entries between `head` and `tail` may have
+// been removed, `length` counts the ones still waiting. `offers` counts the pending
+// offers per tier, whose seats are held back from the public sale.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Waitlist {
+    pub head: u32,
+    pub tail: u32,
+    pub length: u32,
+    pub offers: Map<u32, u32>,
+}
+
+// User waiting for a ticket of a sold-out event. `offer_expires_at` is set once a
+// ticket frees up for them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistEntry {
+    pub user: Address,
+    pub tier_id: u32,
+    pub deposit: i128,
+    pub offer_expires_at: Option<u64>,
+}
+
+// Members-only sale ahead of the public sale. Eligible buyers prove membership with a
+// merkle proof against `merkle_root`, whose leaves are `sha256(buyer.to_xdr())` and
+// whose nodes hash each sorted pair of children.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PresaleConfig {
+    pub merkle_root: BytesN<32>,
+    pub start: u64,
+    pub end: u64,
+    /// General admission price during the presale, if different
+    pub price: Option<i128>,
+    pub public_sale_start: u64,
+}
+
+// General admission price from `starts_at` until the next step
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceStep {
+    pub starts_at: u64,
+    pub price: i128,
+}
+
+// General admission price moving linearly from `start_price` at `start_time` to
+// `end_price` at `end_time`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceRamp {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub start_price: i128,
+    pub end_price: i128,
+}
+
+// How the general admission price changes over time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PriceSchedule {
+    /// Step changes, e.g. early bird -> regular -> door
+    Steps(Vec<PriceStep>),
+    /// Linear ramp between two prices
+    Linear(PriceRamp),
+}
+
+// Price rising by `step` for every ticket sold, capped at `max_price`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinearCurve {
+    pub base_price: i128,
+    pub step: i128,
+    pub max_price: i128,
+}
+
+// Price compounding by `growth_bps` for every ticket sold, capped at `max_price`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExponentialCurve {
+    pub base_price: i128,
+    pub growth_bps: u32,
+    pub max_price: i128,
+}
+
+// General admission price as a function of the number of tickets sold
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BondingCurve {
+    Linear(LinearCurve),
+    Exponential(ExponentialCurve),
+}
+
+// Descending-price sale of a tier. The price falls linearly from `start_price` to
+// `floor_price` over `decay_period` seconds and bidding closes at `end_time`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DutchAuction {
+    pub tier_id: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub decay_period: u64,
+}
+
+// Progress of a Dutch auction. `clearing_price` is set once the auction is settled.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AuctionState {
+    pub bids: u128,
+    pub last_price: i128,
+    pub clearing_price: Option<i128>,
+}
+
+// Lottery allocation of a tier. Registration closes at ledger `close_ledger`, after
+// which winners are drawn from the entrants. `seed_commitment` is the SHA-256 hash of a
+// secret the organizer reveals to draw, committed before anyone enters.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LotteryConfig {
+    pub tier_id: u32,
+    pub close_ledger: u32,
+    pub seed_commitment: BytesN<32>,
+}
+
+// Progress of a lottery. `secret` and `seed` are set once winners have been drawn;
+// the seed reproduces the shuffle of entry indices.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LotteryState {
+    pub entries: u32,
+    pub winners: u32,
+    pub secret: Option<BytesN<32>>,
+    pub seed: Option<BytesN<32>>,
+}
+
+// Discount applied to each ticket bought with a promo code
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Discount {
+    /// Share of the ticket price in basis points
+    Percentage(u32),
+    /// Fixed amount off the ticket price
+    Fixed(i128),
+}
+
+// Promo code registered by the organizer. Each ticket bought with the code uses it
+// once; `tier_id` restricts it to a single tier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromoCode {
+    pub discount: Discount,
+    pub remaining_uses: u32,
+    pub expires_at: Option<u64>,
+    pub tier_id: Option<u32>,
+}
+
+// Optional inputs to a ticket purchase. `proof` is the buyer's allowlist proof during
+// a presale, the purchase fails if the total is above `max_price`, and `promo_code` is
+// the plain code, which is checked against the hashes registered by the organizer.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PurchaseOptions {
+    pub proof: Option<Vec<BytesN<32>>>,
+    pub max_price: Option<i128>,
+    pub promo_code: Option<Bytes>,
+}
+
 #[contract]
 pub struct EventManager;
 
 #[contractimpl]
 impl EventManager {
-    /// Initialize the contract with the ticket factory address
-    pub fn initialize(env: Env, ticket_factory: Address) -> Result<(), Error> {
+    /// Initialize the contract with the ticket factory address and platform admin
+    pub fn initialize(env: Env, ticket_factory: Address, admin: Address) {
         // Ensure not already initialized
         if env.storage().instance().has(&DataKey::TicketFactory) {
-            return Err(Error::AlreadyInitialized);
+            panic!("Already initialized");
         }
 
         // Store the ticket factory address
         env.storage()
             .instance()
             .set(&DataKey::TicketFactory, &ticket_factory);
+        env.storage().instance().set(&DataKey::Admin, &admin);
 
         // Initialize event counter
         env.storage().instance().set(&DataKey::EventCounter, &0u32);
-        
-        Ok(())
     }
 
     /// Create a new event
@@ -79,28 +471,38 @@ impl EventManager {
         end_date: u64,
         ticket_price: i128,
         total_tickets: u128,
-    ) -> Result<u32, Error> {
-      
         payment_token: Address,
+        transfer_policy: TransferPolicy,
     ) -> u32 {
         // Validate organizer address
         organizer.require_auth();
 
         // Validate inputs
-        Self::validate_event_params(&env, start_date, end_date, ticket_price, total_tickets)?;
+        Self::validate_event_params(&env, start_date, end_date, ticket_price, total_tickets);
 
         // Get and increment event counter
-        let event_id = Self::get_and_increment_counter(&env)?;
+        let event_id = Self::get_and_increment_counter(&env);
 
-        // Deploy ticket NFT contract via factory
-        let ticket_nft_addr = Self::deploy_ticket_nft(&env, event_id, theme.clone(), total_tickets)?;
+        // Deploy ticket NFT contract via factory, named after the event
+        let ticket_nft_addr =
+            Self::deploy_ticket_nft(&env, event_id, total_tickets, transfer_policy);
+        env.invoke_contract::<()>(
+            &ticket_nft_addr,
+            &Symbol::new(&env, "set_metadata"),
+            soroban_sdk::vec![
+                &env,
+                theme.into_val(&env),
+                String::from_str(&env, TICKET_SYMBOL).into_val(&env),
+                String::from_str(&env, "").into_val(&env),
+            ],
+        );
 
         // Create event struct
         let event = Event {
             id: event_id,
-            theme: theme.clone(),
+            theme,
             organizer: organizer.clone(),
-            event_type,
+            event_type: event_type.clone(),
             total_tickets,
             tickets_sold: 0,
             ticket_price,
@@ -111,6 +513,32 @@ impl EventManager {
             payment_token,
         };
 
+        // Create the default tier from the event's price and supply
+        let default_tier = TicketTier {
+            id: 0,
+            name: String::from_str(&env, "General Admission"),
+            price: ticket_price,
+            total_supply: total_tickets,
+            sold: 0,
+            sale_start: None,
+            sale_end: None,
+        };
+        Self::save_tier(&env, event_id, &default_tier);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TierCount(event_id), &1u32);
+
+        // Fix the current platform fee for the event's lifetime
+        if let Some(fee) = Self::get_platform_fee(env.clone()) {
+            let fee_key = DataKey::EventFee(event_id);
+            env.storage().persistent().set(&fee_key, &fee);
+            env.storage().persistent().extend_ttl(
+                &fee_key,
+                30 * 24 * 60 * 60 / 5,
+                100 * 24 * 60 * 60 / 5,
+            );
+        }
+
         // Store event
         env.storage()
             .persistent()
@@ -123,21 +551,91 @@ impl EventManager {
             100 * 24 * 60 * 60 / 5, // extend_to (~100 days)
         );
 
+        // Index the event for filtered listings
+        Self::push_index(
+            &env,
+            DataKey::OrganizerEventCount(organizer.clone()),
+            |i| DataKey::OrganizerEvent(organizer.clone(), i),
+            event_id,
+        );
+        Self::push_index(
+            &env,
+            DataKey::TypeEventCount(event_type.clone()),
+            |i| DataKey::TypeEvent(event_type.clone(), i),
+            event_id,
+        );
+        Self::index_dates(&env, event_id, start_date, end_date);
+
         // Emit event creation event
         env.events().publish(
             (Symbol::new(&env, "event_created"),),
             (event_id, organizer, ticket_nft_addr),
         );
 
-        Ok(event_id)
+        event_id
+    }
+
+    /// Get the platform admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Not initialized"))
+    }
+
+    /// Set the platform fee charged on ticket sales. Only the admin can change it, and
+    /// the new fee only applies to events created afterwards.
+    pub fn set_platform_fee(env: Env, fee_bps: u32, flat_fee: i128, treasury: Address) {
+        Self::get_admin(env.clone()).require_auth();
+
+        if fee_bps > MAX_FEE_BPS {
+            panic!("Fee exceeds maximum");
+        }
+        if flat_fee < 0 {
+            panic!("Flat fee cannot be negative");
+        }
+
+        let fee = PlatformFee {
+            fee_bps,
+            flat_fee,
+            treasury: treasury.clone(),
+        };
+        env.storage().instance().set(&DataKey::PlatformFee, &fee);
+
+        // Emit fee update event
+        env.events().publish(
+            (Symbol::new(&env, "platform_fee_updated"),),
+            (fee_bps, flat_fee, treasury),
+        );
+    }
+
+    /// Stop charging a platform fee on events created from now on. Events created
+    /// earlier keep the fee they were created with.
+    pub fn clear_platform_fee(env: Env) {
+        Self::get_admin(env.clone()).require_auth();
+
+        env.storage().instance().remove(&DataKey::PlatformFee);
+
+        env.events()
+            .publish((Symbol::new(&env, "platform_fee_cleared"),), ());
+    }
+
+    /// Get the platform fee applied to newly created events, if any
+    pub fn get_platform_fee(env: Env) -> Option<PlatformFee> {
+        env.storage().instance().get(&DataKey::PlatformFee)
+    }
+
+    /// Get the platform fee charged on an event's ticket sales, if any
+    pub fn get_event_fee(env: Env, event_id: u32) -> Option<PlatformFee> {
+        env.storage().persistent().get(&DataKey::EventFee(event_id))
     }
 
     /// Get event by ID
-    pub fn get_event(env: Env, event_id: u32) -> Result<Event, Error> {
+    pub fn get_event(env: Env, event_id: u32) -> Event {
         env.storage()
             .persistent()
             .get(&DataKey::Event(event_id))
-            .ok_or(Error::EventNotFound)
+            .unwrap_or_else(|| panic!("Event not found"))
     }
 
     /// Get total number of events
@@ -148,7 +646,7 @@ impl EventManager {
             .unwrap_or(0)
     }
 
-    /// Get all events (pagination recommended for production)
+    /// Get all events. Reads every event, so prefer `get_events` in production
     pub fn get_all_events(env: Env) -> Vec<Event> {
         let count = Self::get_event_count(env.clone());
         let mut events = Vec::new(&env);
@@ -162,20 +660,176 @@ impl EventManager {
         events
     }
 
-    /// Cancel an event
-    pub fn cancel_event(env: Env, event_id: u32) -> Result<(), Error> {
-        let mut event: Event = env
-            .storage()
+    /// Get a page of events by ID, starting at `start`. At most 100 events are returned.
+    pub fn get_events(env: Env, start: u32, limit: u32) -> Vec<Event> {
+        let count = Self::get_event_count(env.clone());
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut events = Vec::new(&env);
+
+        for i in start..end {
+            if let Some(event) = env.storage().persistent().get(&DataKey::Event(i)) {
+                events.push_back(event);
+            }
+        }
+
+        events
+    }
+
+    /// Get the number of events created by an organizer
+    pub fn get_organizer_event_count(env: Env, organizer: Address) -> u32 {
+        env.storage()
             .persistent()
-            .get(&DataKey::Event(event_id))
-            .ok_or(Error::EventNotFound)?;
+            .get(&DataKey::OrganizerEventCount(organizer))
+            .unwrap_or(0)
+    }
 
-        // Only organizer can cancel
-        event.organizer.require_auth();
+    /// Get a page of events created by an organizer
+    pub fn get_events_by_organizer(
+        env: Env,
+        organizer: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Event> {
+        Self::read_index_page(
+            &env,
+            DataKey::OrganizerEventCount(organizer.clone()),
+            |i| DataKey::OrganizerEvent(organizer.clone(), i),
+            start,
+            limit,
+        )
+    }
+
+    /// Get the number of events of a given type
+    pub fn get_type_event_count(env: Env, event_type: String) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TypeEventCount(event_type))
+            .unwrap_or(0)
+    }
+
+    /// Get a page of events of a given type
+    pub fn get_events_by_type(env: Env, event_type: String, start: u32, limit: u32) -> Vec<Event> {
+        Self::read_index_page(
+            &env,
+            DataKey::TypeEventCount(event_type.clone()),
+            |i| DataKey::TypeEvent(event_type.clone(), i),
+            start,
+            limit,
+        )
+    }
+
+    /// Get the current status of an event
+    pub fn get_event_status(env: Env, event_id: u32) -> EventStatus {
+        let event = Self::get_event(env.clone(), event_id);
+        Self::status_of(&env, &event)
+    }
+
+    /// Get a page of events with the given status, skipping the first `start` of them.
+    ///
+    /// Canceled events are read from their own index. Upcoming events come soonest
+    /// first and active events by start date, both read from the day buckets of live
+    /// events. Ended events come earliest end first: those already archived, then the
+    /// ones still in the live index. Pages are only short at the end of the listing.
+    pub fn get_events_by_status(
+        env: Env,
+        status: EventStatus,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Event> {
+        let current_time = env.ledger().timestamp();
+        let limit = limit.min(MAX_PAGE_SIZE);
+
+        let mut events = match status {
+            EventStatus::Canceled => {
+                return Self::read_index_page(
+                    &env,
+                    DataKey::StatusEventCount(EventStatus::Canceled),
+                    |i| DataKey::StatusEvent(EventStatus::Canceled, i),
+                    start,
+                    limit,
+                );
+            }
+            EventStatus::Ended => Self::read_index_page(
+                &env,
+                DataKey::StatusEventCount(EventStatus::Ended),
+                |i| DataKey::StatusEvent(EventStatus::Ended, i),
+                start,
+                limit,
+            ),
+            EventStatus::Upcoming | EventStatus::Active => Vec::new(&env),
+        };
+        if events.len() >= limit {
+            return events;
+        }
+
+        // Ended events still in the live index follow the archived ones
+        let mut skip = match status {
+            EventStatus::Ended => start.saturating_sub(
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::StatusEventCount(EventStatus::Ended))
+                    .unwrap_or(0),
+            ),
+            _ => start,
+        };
+        let (index, from) = match status {
+            EventStatus::Upcoming => (DateIndex::Start, current_time),
+            EventStatus::Active => (DateIndex::Start, 0),
+            _ => (DateIndex::End, 0),
+        };
+
+        Self::scan_date_index(&env, index, from, |date, event_id| {
+            let event = match status {
+                EventStatus::Upcoming if date <= current_time => return true,
+                EventStatus::Active | EventStatus::Ended if date > current_time => return false,
+                EventStatus::Active => {
+                    let event = Self::get_event(env.clone(), event_id);
+                    if event.end_date <= current_time {
+                        return true;
+                    }
+                    Some(event)
+                }
+                _ => None,
+            };
+
+            if skip > 0 {
+                skip -= 1;
+            } else {
+                events.push_back(event.unwrap_or_else(|| Self::get_event(env.clone(), event_id)));
+            }
+            events.len() < limit
+        });
+
+        events
+    }
+
+    /// Move up to `limit` ended events out of the date indexes into the ended index,
+    /// keeping the live indexes small. Anyone can call it; index updates also archive
+    /// a small batch. Returns the number of events archived.
+    pub fn archive_ended_events(env: Env, limit: u32) -> u32 {
+        Self::archive_ended(&env, limit)
+    }
+
+    /// Cancel an event. Only the organizer can cancel, since it refunds every holder.
+    pub fn cancel_event(env: Env, caller: Address, event_id: u32) {
+        let mut event = Self::get_event(env.clone(), event_id);
+
+        caller.require_auth();
+        if caller != event.organizer {
+            panic!("Unauthorized");
+        }
 
         // Check if already canceled
         if event.is_canceled {
-            return Err(Error::EventAlreadyCanceled);
+            panic!("Event is already canceled");
+        }
+
+        // Refunds must come out of this event's escrow, so the revenue has to be intact
+        if env.ledger().timestamp() >= event.end_date {
+            panic!("Cannot cancel an event that has ended");
+        }
+        if Self::is_revenue_withdrawn(&env, event_id) {
+            panic!("Cannot cancel after revenue has been withdrawn");
         }
 
         // Mark as canceled
@@ -186,91 +840,2976 @@ impl EventManager {
             .persistent()
             .set(&DataKey::Event(event_id), &event);
 
+        // Move the event from the date indexes to the canceled index
+        Self::unindex_dates(&env, event_id, event.start_date, event.end_date);
+        Self::push_index(
+            &env,
+            DataKey::StatusEventCount(EventStatus::Canceled),
+            |i| DataKey::StatusEvent(EventStatus::Canceled, i),
+            event_id,
+        );
+
         // Emit cancellation event
         env.events()
             .publish((Symbol::new(&env, "event_canceled"),), event_id);
-        
-        Ok(())
     }
 
-    /// Update event details. Only the organizer can update. Cannot update a canceled event.
-    /// Cannot reduce total_tickets below tickets_sold. Cannot set dates in the past.
-    pub fn update_event(
+    /// Reschedule an event. Only the organizer or a co-organizer can reschedule. Existing ticket holders
+    /// can claim a full refund until the refund window closes; after that, unclaimed
+    /// tickets stay valid for the new dates.
+    pub fn reschedule_event(
         env: Env,
+        caller: Address,
         event_id: u32,
-        theme: Option<String>,
-        ticket_price: Option<i128>,
-        total_tickets: Option<u128>,
-        start_date: Option<u64>,
-        end_date: Option<u64>,
+        new_start: u64,
+        new_end: u64,
     ) {
-        let mut event: Event = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Event(event_id))
-            .unwrap_or_else(|| panic!("Event not found"));
+        let mut event = Self::get_event(env.clone(), event_id);
 
-        // Only organizer can update
-        event.organizer.require_auth();
+        // Only organizer or co-organizers can reschedule
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
 
-        // Cannot update a canceled event
         if event.is_canceled {
-            panic!("Cannot update a canceled event");
+            panic!("Cannot reschedule a canceled event");
+        }
+
+        // Reschedule refunds must come out of this event's escrow, so the revenue has
+        // to be intact
+        let current_time = env.ledger().timestamp();
+        if current_time >= event.end_date {
+            panic!("Cannot reschedule an event that has ended");
+        }
+        if Self::is_revenue_withdrawn(&env, event_id) {
+            panic!("Cannot reschedule after revenue has been withdrawn");
+        }
+        if new_start < current_time {
+            panic!("Start date cannot be in the past");
+        }
+        if new_end <= new_start {
+            panic!("End date must be after start date");
+        }
+
+        Self::unindex_dates(&env, event_id, event.start_date, event.end_date);
+        Self::index_dates(&env, event_id, new_start, new_end);
+        event.start_date = new_start;
+        event.end_date = new_end;
+
+        // Open the refund window, closing no later than the new start
+        let window_length = Self::get_reschedule_refund_window(env.clone(), event_id);
+        let window = RescheduleWindow {
+            rescheduled_at: current_time,
+            refund_deadline: current_time.saturating_add(window_length).min(new_start),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(event_id), &event);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Event(event_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RescheduleWindow(event_id), &window);
+        env.storage().persistent().extend_ttl(
+            &DataKey::RescheduleWindow(event_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        Self::sync_transfer_lock(&env, event_id, &event);
+
+        // Emit reschedule event
+        env.events().publish(
+            (Symbol::new(&env, "event_rescheduled"),),
+            (event_id, new_start, new_end, window.refund_deadline),
+        );
+    }
+
+    /// Set how long ticket holders can opt out for a refund after a reschedule.
+    /// Only the organizer or a co-organizer can configure it.
+    pub fn set_reschedule_refund_window(env: Env, caller: Address, event_id: u32, window: u64) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can configure the refund window
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RescheduleRefundWindow(event_id), &window);
+        env.storage().persistent().extend_ttl(
+            &DataKey::RescheduleRefundWindow(event_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+    }
+
+    /// Get the refund window length applied when an event is rescheduled
+    pub fn get_reschedule_refund_window(env: Env, event_id: u32) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RescheduleRefundWindow(event_id))
+            .unwrap_or(DEFAULT_RESCHEDULE_REFUND_WINDOW)
+    }
+
+    /// Get the refund window opened by the latest reschedule of an event, if any
+    pub fn get_reschedule_window(env: Env, event_id: u32) -> Option<RescheduleWindow> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RescheduleWindow(event_id))
+    }
+
+    /// Update event details. Only the organizer or a co-organizer can update. Cannot update a canceled event.
+    /// Cannot reduce total_tickets below tickets_sold. Cannot set dates in the past.
+    pub fn update_event(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        theme: Option<String>,
+        ticket_price: Option<i128>,
+        total_tickets: Option<u128>,
+        start_date: Option<u64>,
+        end_date: Option<u64>,
+    ) {
+        let mut event: Event = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Event(event_id))
+            .unwrap_or_else(|| panic!("Event not found"));
+
+        // Only organizer or co-organizers can update
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        // Cannot update a canceled event
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        let current_time = env.ledger().timestamp();
+        let (old_start, old_end) = (event.start_date, event.end_date);
+
+        // Apply theme if provided
+        if let Some(t) = theme {
+            event.theme = t;
+        }
+
+        // Apply ticket_price if provided (must be non-negative)
+        if let Some(p) = ticket_price {
+            if p < 0 {
+                panic!("Ticket price cannot be negative");
+            }
+            event.ticket_price = p;
+        }
+
+        // Apply total_tickets if provided (cannot be below tickets_sold)
+        if let Some(t) = total_tickets {
+            if t == 0 {
+                panic!("Total tickets must be greater than 0");
+            }
+            if t < event.tickets_sold {
+                panic!("Cannot reduce total_tickets below tickets_sold");
+            }
+            event.total_tickets = t;
+        }
+
+        // Effective end for start validation (new end if provided in this call, else current)
+        let effective_end = end_date.unwrap_or(event.end_date);
+        // Apply start_date if provided
+        if let Some(s) = start_date {
+            if s < current_time {
+                panic!("Start date cannot be in the past");
+            }
+            if s >= effective_end {
+                panic!("Start date must be before end date");
+            }
+            event.start_date = s;
+        }
+
+        // Effective start for end validation (new start if provided in this call, else current)
+        let effective_start = start_date.unwrap_or(event.start_date);
+        // Apply end_date if provided
+        if let Some(e) = end_date {
+            if e < current_time {
+                panic!("End date cannot be in the past");
+            }
+            if e <= effective_start {
+                panic!("End date must be after start date");
+            }
+            event.end_date = e;
+        }
+
+        // Ended events have left the date indexes for good
+        if (start_date.is_some() || end_date.is_some()) && current_time >= old_end {
+            panic!("Cannot reschedule an event that has ended");
+        }
+
+        // Keep the default tier in sync with the event's price and supply
+        if ticket_price.is_some() || total_tickets.is_some() {
+            let mut default_tier = Self::get_ticket_tier(env.clone(), event_id, 0);
+            default_tier.price = event.ticket_price;
+            default_tier.total_supply = event.total_tickets;
+            Self::save_tier(&env, event_id, &default_tier);
+        }
+        if total_tickets.is_some() {
+            Self::sync_max_supply(&env, &event);
+        }
+        if start_date.is_some() || end_date.is_some() {
+            Self::sync_transfer_lock(&env, event_id, &event);
+            Self::unindex_dates(&env, event_id, old_start, old_end);
+            Self::index_dates(&env, event_id, event.start_date, event.end_date);
+        }
+
+        // Update storage
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(event_id), &event);
+
+        // Extend TTL
+        env.storage().persistent().extend_ttl(
+            &DataKey::Event(event_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        // Offer any seats freed by a larger supply to the waitlist
+        if total_tickets.is_some() {
+            Self::advance_waitlist(&env, event_id);
+        }
+
+        // Emit event_updated event
+        env.events().publish(
+            (Symbol::new(&env, "event_updated"),),
+            (event_id, event.organizer.clone()),
+        );
+    }
+
+    /// Update tickets sold (called by ticket purchase logic)
+    pub fn update_tickets_sold(env: Env, event_id: u32, amount: u128) {
+        let mut event = Self::get_event(env.clone(), event_id);
+
+        // Verify the caller (should be the ticket NFT contract or authorized entity)
+        event.ticket_nft_addr.require_auth();
+
+        // Update tickets sold
+        event.tickets_sold = event
+            .tickets_sold
+            .checked_add(amount)
+            .unwrap_or_else(|| panic!("Tickets sold overflow"));
+
+        // Ensure we don't oversell
+        if event.tickets_sold > event.total_tickets {
+            panic!("Cannot sell more tickets");
+        }
+
+        // Update storage
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(event_id), &event);
+    }
+
+    /// Create an additional ticket tier for an event. Only the organizer or a co-organizer can
+    /// create tiers.
+    /// A tier's supply cannot exceed the event's total_tickets.
+    pub fn create_ticket_tier(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        name: String,
+        price: i128,
+        total_supply: u128,
+        sale_start: Option<u64>,
+        sale_end: Option<u64>,
+    ) -> u32 {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can create tiers
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        Self::validate_tier_params(
+            price,
+            total_supply,
+            event.total_tickets,
+            sale_start,
+            sale_end,
+        );
+
+        let tier_id: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TierCount(event_id))
+            .unwrap_or(0);
+
+        let tier = TicketTier {
+            id: tier_id,
+            name,
+            price,
+            total_supply,
+            sold: 0,
+            sale_start,
+            sale_end,
+        };
+        Self::save_tier(&env, event_id, &tier);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TierCount(event_id), &(tier_id + 1));
+        env.storage().persistent().extend_ttl(
+            &DataKey::TierCount(event_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        // Emit tier creation event
+        env.events().publish(
+            (Symbol::new(&env, "tier_created"),),
+            (event_id, tier_id, price, total_supply),
+        );
+
+        tier_id
+    }
+
+    /// Update a ticket tier. Only the organizer or a co-organizer can update. Cannot reduce a tier's
+    /// supply below the number of tickets already sold in it.
+    pub fn update_ticket_tier(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        tier_id: u32,
+        name: Option<String>,
+        price: Option<i128>,
+        total_supply: Option<u128>,
+        sale_start: Option<u64>,
+        sale_end: Option<u64>,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can update tiers
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        let mut tier = Self::get_ticket_tier(env.clone(), event_id, tier_id);
+
+        if let Some(n) = name {
+            tier.name = n;
+        }
+        if let Some(p) = price {
+            tier.price = p;
+        }
+        if let Some(t) = total_supply {
+            if t < tier.sold {
+                panic!("Cannot reduce tier supply below tickets sold");
+            }
+            tier.total_supply = t;
+        }
+        if sale_start.is_some() {
+            tier.sale_start = sale_start;
+        }
+        if sale_end.is_some() {
+            tier.sale_end = sale_end;
+        }
+
+        // The default tier defines the event's supply, other tiers are capped by it
+        let max_supply = if tier_id == 0 {
+            if tier.total_supply < event.tickets_sold {
+                panic!("Cannot reduce total_tickets below tickets_sold");
+            }
+            u128::MAX
+        } else {
+            event.total_tickets
+        };
+
+        Self::validate_tier_params(
+            tier.price,
+            tier.total_supply,
+            max_supply,
+            tier.sale_start,
+            tier.sale_end,
+        );
+        Self::save_tier(&env, event_id, &tier);
+
+        // The default tier mirrors the event's price and supply
+        if tier_id == 0 {
+            let mut event = event;
+            event.ticket_price = tier.price;
+            event.total_tickets = tier.total_supply;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Event(event_id), &event);
+            Self::sync_max_supply(&env, &event);
+            Self::advance_waitlist(&env, event_id);
+        }
+
+        // Emit tier update event
+        env.events()
+            .publish((Symbol::new(&env, "tier_updated"),), (event_id, tier_id));
+    }
+
+    /// Set how many tickets a single wallet can hold for an event (1 by default).
+    /// Only the organizer or a co-organizer can change it.
+    pub fn set_max_tickets_per_wallet(env: Env, caller: Address, event_id: u32, limit: u32) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change the limit
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        env.invoke_contract::<()>(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "set_max_tickets_per_wallet"),
+            soroban_sdk::vec![&env, limit.into_val(&env)],
+        );
+    }
+
+    /// Approve a single transfer of a ticket to a recipient, for events whose tickets
+    /// need organizer approval to change hands. Only the organizer or a co-organizer
+    /// can approve transfers.
+    pub fn approve_ticket_transfer(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        token_id: u128,
+        to: Address,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can approve transfers
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        env.invoke_contract::<()>(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "approve_transfer"),
+            soroban_sdk::vec![&env, token_id.into_val(&env), to.into_val(&env)],
+        );
+    }
+
+    /// Freeze ticket transfers from `window` seconds before the event starts until it
+    /// ends, or lift the freeze with `None`. The freeze follows the event when it is
+    /// rescheduled. Only the organizer or a co-organizer can change it.
+    pub fn set_transfer_lock_window(env: Env, caller: Address, event_id: u32, window: Option<u64>) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change the transfer lock
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        let key = DataKey::TransferLockWindow(event_id);
+        match window {
+            Some(window) => {
+                env.storage().persistent().set(&key, &window);
+                env.storage().persistent().extend_ttl(
+                    &key,
+                    30 * 24 * 60 * 60 / 5,
+                    100 * 24 * 60 * 60 / 5,
+                );
+                Self::sync_transfer_lock(&env, event_id, &event);
+            }
+            None => {
+                env.storage().persistent().remove(&key);
+                env.invoke_contract::<()>(
+                    &event.ticket_nft_addr,
+                    &Symbol::new(&env, "clear_transfer_lock"),
+                    Vec::new(&env),
+                );
+            }
+        }
+    }
+
+    /// Get how long before the start an event's ticket transfers are frozen, if at all
+    pub fn get_transfer_lock_window(env: Env, event_id: u32) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TransferLockWindow(event_id))
+    }
+
+    /// Set the name, symbol and base URI of an event's ticket collection.
+    /// Only the organizer or a co-organizer can change them.
+    pub fn set_ticket_metadata(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        name: String,
+        symbol: String,
+        base_uri: String,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change ticket metadata
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        env.invoke_contract::<()>(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "set_metadata"),
+            soroban_sdk::vec![
+                &env,
+                name.into_val(&env),
+                symbol.into_val(&env),
+                base_uri.into_val(&env),
+            ],
+        );
+    }
+
+    /// Get a ticket tier by event and tier ID
+    pub fn get_ticket_tier(env: Env, event_id: u32, tier_id: u32) -> TicketTier {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TicketTier(event_id, tier_id))
+            .unwrap_or_else(|| panic!("Tier not found"))
+    }
+
+    /// Get all ticket tiers of an event
+    pub fn get_ticket_tiers(env: Env, event_id: u32) -> Vec<TicketTier> {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TierCount(event_id))
+            .unwrap_or(0);
+        let mut tiers = Vec::new(&env);
+
+        for i in 0..count {
+            if let Some(tier) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::TicketTier(event_id, i))
+            {
+                tiers.push_back(tier);
+            }
+        }
+
+        tiers
+    }
+
+    /// Purchase a ticket in the given tier of an event. Payment is held in escrow by
+    /// this contract until the organizer withdraws it after the event ends.
+    pub fn purchase_ticket(
+        env: Env,
+        buyer: Address,
+        event_id: u32,
+        tier_id: u32,
+        options: Option<PurchaseOptions>,
+    ) {
+        buyer.require_auth();
+
+        Self::ensure_public_availability(&env, event_id, tier_id, 1);
+
+        let recipients = soroban_sdk::vec![&env, buyer.clone()];
+        Self::sell_tickets(
+            &env,
+            &buyer,
+            event_id,
+            tier_id,
+            &recipients,
+            &options.unwrap_or_default(),
+        );
+    }
+
+    /// Purchase one ticket for each recipient in a single call. The buyer pays for all
+    /// seats at once, and the whole purchase fails if any recipient already holds a
+    /// ticket, there is not enough supply left, or the total is above the options'
+    /// `max_price`. A promo code is used once per seat.
+    pub fn purchase_tickets(
+        env: Env,
+        buyer: Address,
+        event_id: u32,
+        tier_id: u32,
+        recipients: Vec<Address>,
+        options: Option<PurchaseOptions>,
+    ) -> Vec<u128> {
+        buyer.require_auth();
+
+        if recipients.is_empty() {
+            panic!("No recipients provided");
+        }
+        Self::ensure_public_availability(&env, event_id, tier_id, recipients.len());
+
+        let token_ids = Self::sell_tickets(
+            &env,
+            &buyer,
+            event_id,
+            tier_id,
+            &recipients,
+            &options.unwrap_or_default(),
+        );
+
+        // Emit group purchase event
+        env.events().publish(
+            (Symbol::new(&env, "tickets_purchased"),),
+            (event_id, buyer, token_ids.clone()),
+        );
+
+        token_ids
+    }
+
+    /// Join the waitlist of a sold-out event. With `with_deposit`, the tier's current
+    /// price is held until the user claims a ticket or leaves the waitlist.
+    pub fn join_waitlist(env: Env, user: Address, event_id: u32, tier_id: u32, with_deposit: bool) {
+        user.require_auth();
+
+        let event = Self::get_event(env.clone(), event_id);
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+        if Self::get_waitlist_position(env.clone(), event_id, user.clone()).is_some() {
+            panic!("Already on the waitlist");
+        }
+
+        Self::advance_waitlist(&env, event_id);
+        if Self::public_availability(&env, &event, tier_id) > 0 {
+            panic!("Tickets are still available");
+        }
+
+        let tier = Self::get_ticket_tier(env.clone(), event_id, tier_id);
+        let deposit = if with_deposit { tier.price } else { 0 };
+        if deposit > 0 {
+            let token_client = token::Client::new(&env, &event.payment_token);
+            token_client.transfer(&user, &env.current_contract_address(), &deposit);
+        }
+
+        let mut waitlist = Self::get_waitlist(&env, event_id);
+        let index = waitlist.tail;
+        Self::save_waitlist_entry(
+            &env,
+            event_id,
+            index,
+            &WaitlistEntry {
+                user: user.clone(),
+                tier_id,
+                deposit,
+                offer_expires_at: None,
+            },
+        );
+        let index_key = DataKey::WaitlistIndex(event_id, user.clone());
+        env.storage().persistent().set(&index_key, &index);
+        env.storage().persistent().extend_ttl(
+            &index_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        waitlist.tail += 1;
+        waitlist.length += 1;
+        Self::save_waitlist(&env, event_id, &waitlist);
+
+        env.events().publish(
+            (Symbol::new(&env, "waitlist_joined"),),
+            (event_id, user, deposit),
+        );
+    }
+
+    /// Leave the waitlist, getting any deposit back
+    pub fn leave_waitlist(env: Env, user: Address, event_id: u32) {
+        user.require_auth();
+
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WaitlistIndex(event_id, user.clone()))
+            .unwrap_or_else(|| panic!("Not on the waitlist"));
+        Self::remove_waitlist_entry(&env, event_id, index);
+
+        env.events()
+            .publish((Symbol::new(&env, "waitlist_left"),), (event_id, user));
+    }
+
+    /// Claim the ticket offered to a waitlisted user. The offer must be claimed before
+    /// it expires, after which it passes to the next user in line.
+    pub fn claim_waitlist_ticket(env: Env, user: Address, event_id: u32) -> u128 {
+        user.require_auth();
+
+        Self::advance_waitlist(&env, event_id);
+
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WaitlistIndex(event_id, user.clone()))
+            .unwrap_or_else(|| panic!("Not on the waitlist"));
+        let entry: WaitlistEntry = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WaitlistEntry(event_id, index))
+            .unwrap();
+        if entry.offer_expires_at.is_none() {
+            panic!("No ticket offered yet");
+        }
+
+        // Return the deposit, then charge the current price like a regular purchase
+        Self::remove_waitlist_entry(&env, event_id, index);
+        let recipients = soroban_sdk::vec![&env, user.clone()];
+        let token_id = Self::sell_tickets(
+            &env,
+            &user,
+            event_id,
+            entry.tier_id,
+            &recipients,
+            &PurchaseOptions::default(),
+        )
+        .get(0)
+        .unwrap();
+
+        env.events().publish(
+            (Symbol::new(&env, "waitlist_claimed"),),
+            (event_id, user, token_id),
+        );
+
+        token_id
+    }
+
+    /// Expire unclaimed offers and offer freed tickets to the next users in line.
+    /// Anyone can call it.
+    pub fn process_waitlist(env: Env, event_id: u32) {
+        Self::advance_waitlist(&env, event_id);
+    }
+
+    /// Get a user's 1-based position in an event's waitlist, if they are on it. Users
+    /// who left from the middle of the queue are still counted until the head moves
+    /// past them.
+    pub fn get_waitlist_position(env: Env, event_id: u32, user: Address) -> Option<u32> {
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WaitlistIndex(event_id, user))?;
+        let waitlist = Self::get_waitlist(&env, event_id);
+        Some(index - waitlist.head + 1)
+    }
+
+    /// Get the number of users waiting for a ticket of an event
+    pub fn get_waitlist_length(env: Env, event_id: u32) -> u32 {
+        Self::get_waitlist(&env, event_id).length
+    }
+
+    /// Get a waitlisted user's entry, including any pending offer
+    pub fn get_waitlist_entry(env: Env, event_id: u32, user: Address) -> Option<WaitlistEntry> {
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WaitlistIndex(event_id, user))?;
+        env.storage()
+            .persistent()
+            .get(&DataKey::WaitlistEntry(event_id, index))
+    }
+
+    /// Configure a members-only presale before the public sale opens. Only the organizer
+    /// or a co-organizer can set it.
+    pub fn set_presale(env: Env, caller: Address, event_id: u32, presale: PresaleConfig) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can configure the presale
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+        if presale.start >= presale.end {
+            panic!("Presale must end after it starts");
+        }
+        if presale.public_sale_start < presale.end {
+            panic!("Public sale cannot open before the presale ends");
+        }
+        if presale.price.is_some_and(|price| price < 0) {
+            panic!("Ticket price cannot be negative");
+        }
+
+        let key = DataKey::Presale(event_id);
+        env.storage().persistent().set(&key, &presale);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events().publish(
+            (Symbol::new(&env, "presale_configured"),),
+            (
+                event_id,
+                presale.start,
+                presale.end,
+                presale.public_sale_start,
+            ),
+        );
+    }
+
+    /// Get the presale configuration of an event, if any
+    pub fn get_presale(env: Env, event_id: u32) -> Option<PresaleConfig> {
+        env.storage().persistent().get(&DataKey::Presale(event_id))
+    }
+
+    /// Attach a time-based price schedule to an event's general admission tier, or remove
+    /// it with `None`. Only the organizer or a co-organizer can set it.
+    pub fn set_price_schedule(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        schedule: Option<PriceSchedule>,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change pricing
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        let key = DataKey::PriceSchedule(event_id);
+        let Some(schedule) = schedule else {
+            env.storage().persistent().remove(&key);
+            return;
+        };
+
+        if Self::get_bonding_curve(env.clone(), event_id).is_some() {
+            panic!("Event has a bonding curve");
+        }
+
+        match &schedule {
+            PriceSchedule::Steps(steps) => {
+                if steps.is_empty() {
+                    panic!("Price schedule has no steps");
+                }
+                let mut previous: Option<u64> = None;
+                for step in steps.iter() {
+                    if step.price < 0 {
+                        panic!("Ticket price cannot be negative");
+                    }
+                    if previous.is_some_and(|starts_at| step.starts_at <= starts_at) {
+                        panic!("Price steps must be in chronological order");
+                    }
+                    previous = Some(step.starts_at);
+                }
+            }
+            PriceSchedule::Linear(ramp) => {
+                if ramp.start_price < 0 || ramp.end_price < 0 {
+                    panic!("Ticket price cannot be negative");
+                }
+                if ramp.start_time >= ramp.end_time {
+                    panic!("Price ramp must end after it starts");
+                }
+            }
+        }
+
+        env.storage().persistent().set(&key, &schedule);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events()
+            .publish((Symbol::new(&env, "price_schedule_set"),), event_id);
+    }
+
+    /// Get the price schedule of an event, if any
+    pub fn get_price_schedule(env: Env, event_id: u32) -> Option<PriceSchedule> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PriceSchedule(event_id))
+    }
+
+    /// Attach a demand-based bonding curve to an event's general admission tier, or
+    /// remove it with `None`. The price then rises with the number of tickets sold.
+    /// Only the organizer or a co-organizer can set it.
+    pub fn set_bonding_curve(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        curve: Option<BondingCurve>,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change pricing
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        let key = DataKey::BondingCurve(event_id);
+        let Some(curve) = curve else {
+            env.storage().persistent().remove(&key);
+            return;
+        };
+
+        if Self::get_price_schedule(env.clone(), event_id).is_some() {
+            panic!("Event has a price schedule");
+        }
+
+        let (base_price, max_price) = match &curve {
+            BondingCurve::Linear(curve) => {
+                if curve.step < 0 {
+                    panic!("Curve step cannot be negative");
+                }
+                (curve.base_price, curve.max_price)
+            }
+            BondingCurve::Exponential(curve) => {
+                if curve.growth_bps > MAX_CURVE_GROWTH_BPS {
+                    panic!("Curve growth exceeds maximum");
+                }
+                (curve.base_price, curve.max_price)
+            }
+        };
+        if base_price < 0 {
+            panic!("Ticket price cannot be negative");
+        }
+        if max_price < base_price {
+            panic!("Curve max price is below base price");
+        }
+
+        env.storage().persistent().set(&key, &curve);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events()
+            .publish((Symbol::new(&env, "bonding_curve_set"),), event_id);
+    }
+
+    /// Get the bonding curve of an event, if any
+    pub fn get_bonding_curve(env: Env, event_id: u32) -> Option<BondingCurve> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BondingCurve(event_id))
+    }
+
+    /// Get the general admission price a purchase would be charged right now, taking
+    /// the presale price, bonding curve and price schedule into account
+    pub fn current_price(env: Env, event_id: u32) -> i128 {
+        let event = Self::get_event(env.clone(), event_id);
+        Self::general_admission_price(&env, event_id, event.tickets_sold)
+    }
+
+    /// Get the total price of the next `quantity` general admission tickets. This is
+    /// the list price before any promo code; the platform fee is taken out of it, so it
+    /// is also what the buyer pays without a code.
+    pub fn quote(env: Env, event_id: u32, quantity: u32) -> i128 {
+        let event = Self::get_event(env.clone(), event_id);
+        (0..quantity as u128)
+            .map(|i| Self::general_admission_price(&env, event_id, event.tickets_sold + i))
+            .try_fold(0i128, |total, price| total.checked_add(price))
+            .unwrap_or_else(|| panic!("Quote overflow"))
+    }
+
+    /// General admission price of the ticket sold after `sold` others
+    fn general_admission_price(env: &Env, event_id: u32, sold: u128) -> i128 {
+        let current_time = env.ledger().timestamp();
+
+        if let Some(presale) = Self::get_presale(env.clone(), event_id) {
+            if let Some(price) = presale.price {
+                if current_time >= presale.start && current_time < presale.end {
+                    return price;
+                }
+            }
+        }
+
+        match Self::get_bonding_curve(env.clone(), event_id) {
+            Some(BondingCurve::Linear(curve)) => {
+                return curve
+                    .step
+                    .checked_mul(sold as i128)
+                    .and_then(|increase| curve.base_price.checked_add(increase))
+                    .map_or(curve.max_price, |price| price.min(curve.max_price));
+            }
+            Some(BondingCurve::Exponential(curve)) => {
+                return Self::exponential_price(&curve, sold);
+            }
+            None => {}
+        }
+
+        let base_price = Self::get_ticket_tier(env.clone(), event_id, 0).price;
+        match Self::get_price_schedule(env.clone(), event_id) {
+            None => base_price,
+            Some(PriceSchedule::Steps(steps)) => steps
+                .iter()
+                .filter(|step| step.starts_at <= current_time)
+                .last()
+                .map_or(base_price, |step| step.price),
+            Some(PriceSchedule::Linear(ramp)) => {
+                if current_time <= ramp.start_time {
+                    ramp.start_price
+                } else if current_time >= ramp.end_time {
+                    ramp.end_price
+                } else {
+                    let elapsed = (current_time - ramp.start_time) as i128;
+                    let duration = (ramp.end_time - ramp.start_time) as i128;
+                    ramp.start_price + (ramp.end_price - ramp.start_price) * elapsed / duration
+                }
+            }
+        }
+    }
+
+    /// Sell one of an event's tiers through a descending-price Dutch auction instead of
+    /// fixed-price purchases. Only the organizer or a co-organizer can set it, and only
+    /// before the first bid.
+    pub fn set_dutch_auction(env: Env, caller: Address, event_id: u32, auction: DutchAuction) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change the sale
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        // Ensure the tier exists
+        Self::get_ticket_tier(env.clone(), event_id, auction.tier_id);
+
+        if Self::get_auction_state(env.clone(), event_id).is_some() {
+            panic!("Auction already has bids");
+        }
+        if auction.floor_price < 0 {
+            panic!("Ticket price cannot be negative");
+        }
+        if auction.start_price < auction.floor_price {
+            panic!("Auction start price is below floor price");
+        }
+        if auction.decay_period == 0 {
+            panic!("Auction decay period must be greater than 0");
+        }
+        if auction.end_time <= auction.start_time {
+            panic!("Auction must end after it starts");
+        }
+
+        let key = DataKey::DutchAuction(event_id);
+        env.storage().persistent().set(&key, &auction);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events().publish(
+            (Symbol::new(&env, "auction_created"),),
+            (event_id, auction.tier_id),
+        );
+    }
+
+    /// Get the Dutch auction of an event, if any
+    pub fn get_dutch_auction(env: Env, event_id: u32) -> Option<DutchAuction> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DutchAuction(event_id))
+    }
+
+    /// Get the progress of an event's Dutch auction, if it has received bids
+    pub fn get_auction_state(env: Env, event_id: u32) -> Option<AuctionState> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AuctionState(event_id))
+    }
+
+    /// Get the current Dutch auction price of an event
+    pub fn auction_price(env: Env, event_id: u32) -> i128 {
+        let auction = Self::get_dutch_auction(env.clone(), event_id)
+            .unwrap_or_else(|| panic!("Event has no auction"));
+
+        let current_time = env.ledger().timestamp();
+        if current_time <= auction.start_time {
+            return auction.start_price;
+        }
+
+        let elapsed = current_time - auction.start_time;
+        if elapsed >= auction.decay_period {
+            return auction.floor_price;
+        }
+
+        let decay = (auction.start_price - auction.floor_price) * elapsed as i128
+            / auction.decay_period as i128;
+        auction.start_price - decay
+    }
+
+    /// Bid for one auctioned ticket at the current price. The bid is held in escrow and
+    /// any amount above the clearing price is refunded when the ticket is claimed.
+    pub fn place_bid(env: Env, bidder: Address, event_id: u32) -> i128 {
+        bidder.require_auth();
+
+        let mut event = Self::get_event(env.clone(), event_id);
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+
+        let auction = Self::get_dutch_auction(env.clone(), event_id)
+            .unwrap_or_else(|| panic!("Event has no auction"));
+        let current_time = env.ledger().timestamp();
+        if current_time < auction.start_time {
+            panic!("Auction has not started");
+        }
+        if current_time >= auction.end_time {
+            panic!("Auction has ended");
+        }
+
+        let bid_key = DataKey::AuctionBid(event_id, bidder.clone());
+        if env.storage().persistent().has(&bid_key) {
+            panic!("Already bid in this auction");
+        }
+        if !Self::has_wallet_room(&env, &event, &bidder) {
+            panic!("Wallet ticket limit reached");
+        }
+
+        let mut tier = Self::get_ticket_tier(env.clone(), event_id, auction.tier_id);
+        if tier.sold >= tier.total_supply || event.tickets_sold >= event.total_tickets {
+            panic!("Auction is sold out");
+        }
+        Self::ensure_public_availability(&env, event_id, auction.tier_id, 1);
+
+        // Escrow the bid until the auction is settled
+        let price = Self::auction_price(env.clone(), event_id);
+        if price > 0 {
+            let token_client = token::Client::new(&env, &event.payment_token);
+            token_client.transfer(&bidder, &env.current_contract_address(), &price);
+        }
+
+        env.storage().persistent().set(&bid_key, &price);
+        env.storage().persistent().extend_ttl(
+            &bid_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        let mut state = Self::get_auction_state(env.clone(), event_id).unwrap_or_default();
+        state.bids += 1;
+        state.last_price = price;
+        Self::save_auction_state(&env, event_id, &state);
+        Self::lock_revenue_splits(&env, event_id);
+
+        // Reserve the seat; the ticket is minted when the bidder claims it
+        tier.sold += 1;
+        Self::save_tier(&env, event_id, &tier);
+        event.tickets_sold += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(event_id), &event);
+
+        env.events().publish(
+            (Symbol::new(&env, "auction_bid"),),
+            (event_id, bidder, price),
+        );
+
+        price
+    }
+
+    /// Close an event's Dutch auction once it has sold out or ended. Every bidder pays
+    /// the final clearing price, which moves into the event's escrowed revenue.
+    pub fn settle_auction(env: Env, event_id: u32) -> i128 {
+        let event = Self::get_event(env.clone(), event_id);
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+
+        let auction = Self::get_dutch_auction(env.clone(), event_id)
+            .unwrap_or_else(|| panic!("Event has no auction"));
+        let mut state = Self::get_auction_state(env.clone(), event_id).unwrap_or_default();
+        if state.clearing_price.is_some() {
+            panic!("Auction already settled");
+        }
+
+        let tier = Self::get_ticket_tier(env.clone(), event_id, auction.tier_id);
+        let sold_out = tier.sold >= tier.total_supply;
+        if !sold_out && env.ledger().timestamp() < auction.end_time {
+            panic!("Auction is still running");
+        }
+
+        // The price only falls, so the last bid sets the clearing price
+        let clearing_price = if state.bids > 0 {
+            state.last_price
+        } else {
+            auction.floor_price
+        };
+        state.clearing_price = Some(clearing_price);
+        Self::save_auction_state(&env, event_id, &state);
+
+        if state.bids > 0 {
+            let fee = Self::get_event_fee(env.clone(), event_id);
+            let fee_per_ticket = Self::fee_per_ticket(&fee, clearing_price);
+            Self::adjust_balance(
+                &env,
+                event_id,
+                (clearing_price - fee_per_ticket) * state.bids as i128,
+            );
+            Self::adjust_fees(&env, event_id, fee_per_ticket * state.bids as i128);
+            Self::lock_revenue_splits(&env, event_id);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "auction_settled"),),
+            (event_id, clearing_price, state.bids),
+        );
+
+        clearing_price
+    }
+
+    /// Claim the ticket won in a settled Dutch auction along with the refund of any
+    /// amount bid above the clearing price. If the event was canceled, or the bidder's
+    /// wallet has filled up since bidding, the whole bid is refunded instead and no
+    /// ticket is minted.
+    pub fn claim_auction_ticket(env: Env, bidder: Address, event_id: u32) -> Option<u128> {
+        bidder.require_auth();
+
+        let mut event = Self::get_event(env.clone(), event_id);
+        let bid_key = DataKey::AuctionBid(event_id, bidder.clone());
+        let paid: i128 = env
+            .storage()
+            .persistent()
+            .get(&bid_key)
+            .unwrap_or_else(|| panic!("No bid to claim"));
+
+        env.storage().persistent().remove(&bid_key);
+
+        let clearing_price =
+            Self::get_auction_state(env.clone(), event_id).and_then(|state| state.clearing_price);
+        if !event.is_canceled && clearing_price.is_none() {
+            panic!("Auction is not settled");
+        }
+        let auction = Self::get_dutch_auction(env.clone(), event_id)
+            .unwrap_or_else(|| panic!("Event has no auction"));
+        let fee = Self::get_event_fee(env.clone(), event_id);
+        let token_client = token::Client::new(&env, &event.payment_token);
+
+        // A bidder who can't hold another ticket gives the seat back
+        let wallet_full = !event.is_canceled && !Self::has_wallet_room(&env, &event, &bidder);
+        if wallet_full {
+            Self::free_seat(&env, event_id, &mut event, auction.tier_id);
+            Self::advance_waitlist(&env, event_id);
+        }
+
+        if event.is_canceled || wallet_full {
+            // Once settled, the clearing price is escrowed revenue and fees
+            if let Some(clearing_price) = clearing_price {
+                let fee_per_ticket = Self::fee_per_ticket(&fee, clearing_price);
+                Self::adjust_balance(&env, event_id, -(clearing_price - fee_per_ticket));
+                Self::adjust_fees(&env, event_id, -fee_per_ticket);
+            }
+            if paid > 0 {
+                token_client.transfer(&env.current_contract_address(), &bidder, &paid);
+            }
+            env.events().publish(
+                (Symbol::new(&env, "auction_bid_refunded"),),
+                (event_id, bidder, paid),
+            );
+            return None;
+        }
+
+        let clearing_price = clearing_price.unwrap();
+        let token_id = env.invoke_contract::<u128>(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "mint_ticket_nft"),
+            soroban_sdk::vec![&env, bidder.into_val(&env), auction.tier_id.into_val(&env)],
+        );
+
+        // Record what was paid so refunds don't depend on the current price
+        let payment_key = DataKey::TicketPayment(event_id, token_id);
+        env.storage()
+            .persistent()
+            .set(&payment_key, &clearing_price);
+        env.storage().persistent().extend_ttl(
+            &payment_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        let purchased_at_key = DataKey::TicketPurchasedAt(event_id, token_id);
+        env.storage()
+            .persistent()
+            .set(&purchased_at_key, &env.ledger().timestamp());
+        env.storage().persistent().extend_ttl(
+            &purchased_at_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        let refund = paid - clearing_price;
+        if refund > 0 {
+            token_client.transfer(&env.current_contract_address(), &bidder, &refund);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "auction_ticket_claimed"),),
+            (event_id, bidder, token_id, refund),
+        );
+
+        Some(token_id)
+    }
+
+    /// Allocate one of an event's tiers by lottery instead of first-come-first-served.
+    /// Only the organizer or a co-organizer can set it, and only before the first entry,
+    /// so the seed commitment is fixed before anyone enters.
+    pub fn set_lottery(env: Env, caller: Address, event_id: u32, config: LotteryConfig) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change the sale
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        // Ensure the tier exists
+        Self::get_ticket_tier(env.clone(), event_id, config.tier_id);
+
+        if Self::get_lottery_state(env.clone(), event_id).is_some() {
+            panic!("Lottery already has entries");
+        }
+        if config.close_ledger <= env.ledger().sequence() {
+            panic!("Lottery must close in a future ledger");
+        }
+
+        let key = DataKey::Lottery(event_id);
+        env.storage().persistent().set(&key, &config);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events().publish(
+            (Symbol::new(&env, "lottery_created"),),
+            (event_id, config.tier_id, config.close_ledger),
+        );
+    }
+
+    /// Get the lottery of an event, if any
+    pub fn get_lottery(env: Env, event_id: u32) -> Option<LotteryConfig> {
+        env.storage().persistent().get(&DataKey::Lottery(event_id))
+    }
+
+    /// Get the progress of an event's lottery, if it has received entries or been drawn
+    pub fn get_lottery_state(env: Env, event_id: u32) -> Option<LotteryState> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LotteryState(event_id))
+    }
+
+    /// Enter an event's lottery, escrowing the tier price as a deposit. Returns the
+    /// entry index.
+    pub fn enter_lottery(env: Env, user: Address, event_id: u32) -> u32 {
+        user.require_auth();
+
+        let event = Self::get_event(env.clone(), event_id);
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+
+        let config = Self::get_lottery(env.clone(), event_id)
+            .unwrap_or_else(|| panic!("Event has no lottery"));
+        if env.ledger().sequence() >= config.close_ledger {
+            panic!("Lottery registration is closed");
+        }
+
+        let deposit_key = DataKey::LotteryDeposit(event_id, user.clone());
+        if env.storage().persistent().has(&deposit_key) {
+            panic!("Already entered the lottery");
+        }
+        if !Self::has_wallet_room(&env, &event, &user) {
+            panic!("Wallet ticket limit reached");
+        }
+
+        let deposit = Self::get_ticket_tier(env.clone(), event_id, config.tier_id).price;
+        if deposit > 0 {
+            let token_client = token::Client::new(&env, &event.payment_token);
+            token_client.transfer(&user, &env.current_contract_address(), &deposit);
+        }
+
+        env.storage().persistent().set(&deposit_key, &deposit);
+        env.storage().persistent().extend_ttl(
+            &deposit_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        let mut state = Self::get_lottery_state(env.clone(), event_id).unwrap_or_default();
+        let index = state.entries;
+        let entry_key = DataKey::LotteryEntry(event_id, index);
+        env.storage().persistent().set(&entry_key, &user);
+        env.storage().persistent().extend_ttl(
+            &entry_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+        state.entries += 1;
+        Self::save_lottery_state(&env, event_id, &state);
+
+        env.events().publish(
+            (Symbol::new(&env, "lottery_entered"),),
+            (event_id, user, index),
+        );
+
+        index
+    }
+
+    /// Draw an event's lottery once registration has closed by revealing the secret
+    /// behind its seed commitment. The seed is the hash of the secret, contract, event,
+    /// closing ledger and entry count, so it is fixed before the draw and anyone can
+    /// re-run it from on-chain data. Entry indices are shuffled and the first ones up
+    /// to the available seats win. Returns the number of winners.
+    pub fn draw_lottery(env: Env, event_id: u32, secret: BytesN<32>) -> u32 {
+        let mut event = Self::get_event(env.clone(), event_id);
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+
+        let config = Self::get_lottery(env.clone(), event_id)
+            .unwrap_or_else(|| panic!("Event has no lottery"));
+        if env.ledger().sequence() < config.close_ledger {
+            panic!("Lottery registration is still open");
+        }
+
+        let mut state = Self::get_lottery_state(env.clone(), event_id).unwrap_or_default();
+        if state.seed.is_some() {
+            panic!("Lottery already drawn");
+        }
+
+        let commitment: BytesN<32> = env.crypto().sha256(&secret.clone().into()).into();
+        if commitment != config.seed_commitment {
+            panic!("Secret does not match the seed commitment");
+        }
+
+        let seed: BytesN<32> = env
+            .crypto()
+            .sha256(
+                &(
+                    secret.clone(),
+                    env.current_contract_address(),
+                    event_id,
+                    config.close_ledger,
+                    state.entries,
+                )
+                    .to_xdr(&env),
+            )
+            .into();
+
+        let mut tier = Self::get_ticket_tier(env.clone(), event_id, config.tier_id);
+        let seats = Self::public_availability(&env, &event, config.tier_id);
+        let winners = (state.entries as u128).min(seats) as u32;
+
+        let mut indices = Vec::new(&env);
+        for index in 0..state.entries {
+            indices.push_back(index);
+        }
+        env.prng().seed(seed.clone().into());
+        env.prng().shuffle(&mut indices);
+
+        for index in indices.iter().take(winners as usize) {
+            let user: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::LotteryEntry(event_id, index))
+                .unwrap();
+            let winner_key = DataKey::LotteryWinner(event_id, user.clone());
+            env.storage().persistent().set(&winner_key, &true);
+            env.storage().persistent().extend_ttl(
+                &winner_key,
+                30 * 24 * 60 * 60 / 5,
+                100 * 24 * 60 * 60 / 5,
+            );
+
+            env.events().publish(
+                (Symbol::new(&env, "lottery_winner"),),
+                (event_id, user, index),
+            );
+        }
+
+        state.winners = winners;
+        state.secret = Some(secret);
+        state.seed = Some(seed.clone());
+        Self::save_lottery_state(&env, event_id, &state);
+
+        // Reserve the seats; tickets are minted when winners claim them
+        tier.sold += winners as u128;
+        Self::save_tier(&env, event_id, &tier);
+        event.tickets_sold += winners as u128;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(event_id), &event);
+
+        env.events().publish(
+            (Symbol::new(&env, "lottery_drawn"),),
+            (event_id, seed, state.entries, winners),
+        );
+
+        winners
+    }
+
+    /// Check whether a user won an event's lottery
+    pub fn is_lottery_winner(env: Env, event_id: u32, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LotteryWinner(event_id, user))
+            .unwrap_or(false)
+    }
+
+    /// Settle a lottery entry after the draw. Winners are minted a ticket paid for by
+    /// their deposit; everyone else, or every entrant of a canceled event, gets the
+    /// deposit back.
+    pub fn claim_lottery(env: Env, user: Address, event_id: u32) -> Option<u128> {
+        user.require_auth();
+
+        let mut event = Self::get_event(env.clone(), event_id);
+        let deposit_key = DataKey::LotteryDeposit(event_id, user.clone());
+        let deposit: i128 = env
+            .storage()
+            .persistent()
+            .get(&deposit_key)
+            .unwrap_or_else(|| panic!("No lottery entry to claim"));
+
+        let drawn = Self::get_lottery_state(env.clone(), event_id)
+            .is_some_and(|state| state.seed.is_some());
+        if !drawn && !event.is_canceled {
+            panic!("Lottery has not been drawn");
+        }
+
+        env.storage().persistent().remove(&deposit_key);
+        let token_client = token::Client::new(&env, &event.payment_token);
+
+        let config = Self::get_lottery(env.clone(), event_id)
+            .unwrap_or_else(|| panic!("Event has no lottery"));
+        let won =
+            !event.is_canceled && Self::is_lottery_winner(env.clone(), event_id, user.clone());
+
+        // A winner whose wallet filled up since entering gets the deposit back, and the
+        // reserved seat goes back on sale
+        let wallet_full = won && !Self::has_wallet_room(&env, &event, &user);
+        if wallet_full {
+            Self::free_seat(&env, event_id, &mut event, config.tier_id);
+            Self::advance_waitlist(&env, event_id);
+        }
+
+        if !won || wallet_full {
+            if deposit > 0 {
+                token_client.transfer(&env.current_contract_address(), &user, &deposit);
+            }
+            env.events().publish(
+                (Symbol::new(&env, "lottery_deposit_refunded"),),
+                (event_id, user, deposit),
+            );
+            return None;
+        }
+
+        let token_id = env.invoke_contract::<u128>(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "mint_ticket_nft"),
+            soroban_sdk::vec![&env, user.into_val(&env), config.tier_id.into_val(&env)],
+        );
+
+        // The deposit pays for the ticket
+        let fee = Self::get_event_fee(env.clone(), event_id);
+        let fee_per_ticket = Self::fee_per_ticket(&fee, deposit);
+        Self::adjust_balance(&env, event_id, deposit - fee_per_ticket);
+        Self::adjust_fees(&env, event_id, fee_per_ticket);
+        Self::lock_revenue_splits(&env, event_id);
+
+        let payment_key = DataKey::TicketPayment(event_id, token_id);
+        env.storage().persistent().set(&payment_key, &deposit);
+        env.storage().persistent().extend_ttl(
+            &payment_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        let purchased_at_key = DataKey::TicketPurchasedAt(event_id, token_id);
+        env.storage()
+            .persistent()
+            .set(&purchased_at_key, &env.ledger().timestamp());
+        env.storage().persistent().extend_ttl(
+            &purchased_at_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "ticket_purchased"),),
+            (event_id, user, event.ticket_nft_addr.clone()),
+        );
+
+        Some(token_id)
+    }
+
+    /// Register a promo code for an event by the SHA-256 hash of the code, so the code
+    /// itself is never stored on-chain. Only the organizer or a co-organizer can add it.
+    pub fn add_promo_code(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        code_hash: BytesN<32>,
+        promo: PromoCode,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can manage promo codes
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if event.is_canceled {
+            panic!("Cannot update a canceled event");
+        }
+
+        let key = DataKey::PromoCode(event_id, code_hash.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("Promo code already exists");
+        }
+
+        match promo.discount {
+            Discount::Percentage(bps) if bps as i128 > BPS_DENOMINATOR => {
+                panic!("Discount exceeds ticket price")
+            }
+            Discount::Fixed(amount) if amount < 0 => panic!("Discount cannot be negative"),
+            _ => {}
+        }
+        if promo.remaining_uses == 0 {
+            panic!("Promo code must have at least one use");
+        }
+        if let Some(tier_id) = promo.tier_id {
+            // Ensure the tier exists
+            Self::get_ticket_tier(env.clone(), event_id, tier_id);
+        }
+
+        env.storage().persistent().set(&key, &promo);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events()
+            .publish((Symbol::new(&env, "promo_code_added"), event_id), code_hash);
+    }
+
+    /// Remove a promo code from an event. Only the organizer or a co-organizer can
+    /// remove it.
+    pub fn remove_promo_code(env: Env, caller: Address, event_id: u32, code_hash: BytesN<32>) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can manage promo codes
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PromoCode(event_id, code_hash.clone()));
+
+        env.events().publish(
+            (Symbol::new(&env, "promo_code_removed"), event_id),
+            code_hash,
+        );
+    }
+
+    /// Get a promo code of an event by its hash, if any
+    pub fn get_promo_code(env: Env, event_id: u32, code_hash: BytesN<32>) -> Option<PromoCode> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PromoCode(event_id, code_hash))
+    }
+
+    /// Get the ticket revenue currently held in escrow for an event
+    pub fn get_event_balance(env: Env, event_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EventBalance(event_id))
+            .unwrap_or(0)
+    }
+
+    /// Get the platform fees held in escrow for an event
+    pub fn get_event_fees(env: Env, event_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EventFees(event_id))
+            .unwrap_or(0)
+    }
+
+    /// Pay an event's escrowed platform fees to the treasury once the event has ended.
+    /// Withdrawing revenue does this as well. Anyone can trigger it.
+    pub fn collect_platform_fees(env: Env, event_id: u32) -> i128 {
+        let event = Self::get_event(env.clone(), event_id);
+        Self::require_settleable(&env, &event);
+
+        let amount = Self::pay_platform_fees(&env, event_id, &event);
+        if amount <= 0 {
+            panic!("No fees to collect");
+        }
+
+        amount
+    }
+
+    /// Withdraw escrowed ticket revenue to the organizer, or to every payee when the
+    /// event has revenue splits. Only the organizer or finance staff can withdraw, only
+    /// once the event has ended, and never for a canceled event.
+    pub fn withdraw_funds(env: Env, caller: Address, event_id: u32) -> i128 {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or finance staff can withdraw
+        Self::require_role(&env, event_id, &event, &caller, Role::Finance);
+        Self::require_settleable(&env, &event);
+        Self::pay_platform_fees(&env, event_id, &event);
+
+        let amount = Self::get_event_balance(env.clone(), event_id);
+        if amount <= 0 {
+            panic!("No funds to withdraw");
+        }
+
+        // Settle every payee's outstanding share
+        if let Some(splits) = Self::get_revenue_splits(env.clone(), event_id) {
+            let mut total = 0;
+            for split in splits.iter() {
+                total += Self::pay_revenue_share(&env, event_id, &event, &split);
+            }
+            return total;
+        }
+
+        // Clear the balance before paying out
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventBalance(event_id), &0i128);
+        Self::mark_revenue_withdrawn(&env, event_id);
+
+        let token_client = token::Client::new(&env, &event.payment_token);
+        token_client.transfer(&env.current_contract_address(), &event.organizer, &amount);
+
+        // Emit withdrawal event
+        env.events().publish(
+            (Symbol::new(&env, "funds_withdrawn"),),
+            (event_id, event.organizer, amount),
+        );
+
+        amount
+    }
+
+    /// Split an event's revenue between payees. Shares are in basis points and must sum
+    /// to 10000. Only the organizer can set splits, and only before the first ticket sells.
+    pub fn set_revenue_splits(env: Env, event_id: u32, splits: Vec<RevenueSplit>) {
+        let event = Self::get_event(env.clone(), event_id);
+        event.organizer.require_auth();
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::SplitsLocked(event_id))
+        {
+            panic!("Revenue splits are locked");
+        }
+
+        let mut total_bps = 0u32;
+        for (i, split) in splits.iter().enumerate() {
+            if split.bps == 0 {
+                panic!("Split share must be greater than 0");
+            }
+            if splits
+                .iter()
+                .skip(i + 1)
+                .any(|other| other.payee == split.payee)
+            {
+                panic!("Duplicate payee");
+            }
+            total_bps += split.bps;
+        }
+        if total_bps != BPS_DENOMINATOR as u32 {
+            panic!("Split shares must sum to 10000");
+        }
+
+        let key = DataKey::RevenueSplits(event_id);
+        env.storage().persistent().set(&key, &splits);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events()
+            .publish((Symbol::new(&env, "revenue_splits_set"),), event_id);
+    }
+
+    /// Get an event's revenue split table, if it has one
+    pub fn get_revenue_splits(env: Env, event_id: u32) -> Option<Vec<RevenueSplit>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RevenueSplits(event_id))
+    }
+
+    /// Get how much revenue has been paid out to a payee of an event
+    pub fn get_revenue_paid(env: Env, event_id: u32, payee: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SplitPaid(event_id, payee))
+            .unwrap_or(0)
+    }
+
+    /// Pull a payee's outstanding share of an event's revenue, once the event has ended
+    pub fn claim_revenue_share(env: Env, payee: Address, event_id: u32) -> i128 {
+        payee.require_auth();
+
+        let event = Self::get_event(env.clone(), event_id);
+        Self::require_settleable(&env, &event);
+        Self::pay_platform_fees(&env, event_id, &event);
+
+        let split = Self::get_revenue_splits(env.clone(), event_id)
+            .and_then(|splits| splits.iter().find(|split| split.payee == payee))
+            .unwrap_or_else(|| panic!("Not a payee of this event"));
+
+        let amount = Self::pay_revenue_share(&env, event_id, &event, &split);
+        if amount <= 0 {
+            panic!("No funds to withdraw");
+        }
+
+        amount
+    }
+
+    /// Get the amount paid for a ticket, including the platform fee, if it was bought
+    /// through this contract
+    pub fn get_ticket_payment(env: Env, event_id: u32, token_id: u128) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TicketPayment(event_id, token_id))
+    }
+
+    /// Check whether the refund for a ticket has already been claimed
+    pub fn is_refund_claimed(env: Env, event_id: u32, token_id: u128) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefundClaimed(event_id, token_id))
+            .unwrap_or(false)
+    }
+
+    /// Claim a refund for a ticket of a canceled event, or of a rescheduled event while
+    /// its refund window is open. The current ticket owner receives exactly what was
+    /// paid for the ticket, platform fee included, and the ticket is burned.
+    pub fn claim_refund(env: Env, event_id: u32, token_id: u128) -> i128 {
+        let mut event = Self::get_event(env.clone(), event_id);
+
+        if !event.is_canceled && !Self::in_reschedule_window(&env, event_id, token_id) {
+            panic!("Event is not canceled");
+        }
+
+        if Self::is_refund_claimed(env.clone(), event_id, token_id) {
+            panic!("Refund already claimed");
+        }
+
+        let amount = Self::get_ticket_payment(env.clone(), event_id, token_id)
+            .unwrap_or_else(|| panic!("Ticket not purchased through this event"));
+
+        if Self::is_checked_in(env.clone(), event_id, token_id) {
+            panic!("Ticket already checked in");
+        }
+
+        // Never pay a refund out of another event's escrow
+        let fee = Self::fee_per_ticket(&Self::get_event_fee(env.clone(), event_id), amount);
+        if Self::get_event_balance(env.clone(), event_id) < amount - fee
+            || Self::get_event_fees(env.clone(), event_id) < fee
+        {
+            panic!("Insufficient escrow for refund");
+        }
+
+        // Only the current ticket owner can claim
+        let owner: Address = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "owner_of"),
+            soroban_sdk::vec![&env, token_id.into_val(&env)],
+        );
+        owner.require_auth();
+
+        // Mark as refunded before any external transfer
+        let claimed_key = DataKey::RefundClaimed(event_id, token_id);
+        env.storage().persistent().set(&claimed_key, &true);
+        env.storage().persistent().extend_ttl(
+            &claimed_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        // Burn the ticket so it can no longer be used or resold
+        Self::release_seat(&env, event_id, &mut event, token_id);
+
+        if amount > 0 {
+            Self::adjust_balance(&env, event_id, -(amount - fee));
+            Self::adjust_fees(&env, event_id, -fee);
+            let token_client = token::Client::new(&env, &event.payment_token);
+            token_client.transfer(&env.current_contract_address(), &owner, &amount);
+        }
+
+        // Emit refund event
+        env.events().publish(
+            (Symbol::new(&env, "refund_claimed"),),
+            (event_id, token_id, owner, amount),
+        );
+
+        // Offer the freed seat to the waitlist
+        if !event.is_canceled {
+            Self::advance_waitlist(&env, event_id);
+        }
+
+        amount
+    }
+
+    /// Burn a ticket, freeing its seat for the waitlist or new buyers. Nothing is
+    /// refunded. Tickets can only be burned through here, so the seat count stays in
+    /// sync with the ticket contract.
+    pub fn burn_ticket(env: Env, event_id: u32, token_id: u128) {
+        let mut event = Self::get_event(env.clone(), event_id);
+
+        let owner: Address = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "owner_of"),
+            soroban_sdk::vec![&env, token_id.into_val(&env)],
+        );
+        owner.require_auth();
+
+        Self::release_seat(&env, event_id, &mut event, token_id);
+
+        env.events().publish(
+            (Symbol::new(&env, "ticket_burned"),),
+            (event_id, token_id, owner),
+        );
+
+        if !event.is_canceled {
+            Self::advance_waitlist(&env, event_id);
+        }
+    }
+
+    /// Grant a staff role for an event. Only the organizer can manage roles.
+    pub fn grant_role(env: Env, event_id: u32, account: Address, role: Role) {
+        let event = Self::get_event(env.clone(), event_id);
+        event.organizer.require_auth();
+
+        let key = DataKey::Role(event_id, account.clone(), role);
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events().publish(
+            (Symbol::new(&env, "role_granted"),),
+            (event_id, account, role),
+        );
+    }
+
+    /// Revoke a staff role for an event. Only the organizer can manage roles.
+    pub fn revoke_role(env: Env, event_id: u32, account: Address, role: Role) {
+        let event = Self::get_event(env.clone(), event_id);
+        event.organizer.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Role(event_id, account.clone(), role));
+
+        env.events().publish(
+            (Symbol::new(&env, "role_revoked"),),
+            (event_id, account, role),
+        );
+    }
+
+    /// Check whether an account holds a staff role for an event
+    pub fn has_role(env: Env, event_id: u32, account: Address, role: Role) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Role(event_id, account, role))
+            .unwrap_or(false)
+    }
+
+    /// Get all staff roles an account holds for an event
+    pub fn get_roles(env: Env, event_id: u32, account: Address) -> Vec<Role> {
+        let mut roles = Vec::new(&env);
+        for role in [Role::CoOrganizer, Role::Scanner, Role::Finance] {
+            if Self::has_role(env.clone(), event_id, account.clone(), role) {
+                roles.push_back(role);
+            }
+        }
+        roles
+    }
+
+    /// Redeem a ticket at the door. Only the organizer and scanners can check in tickets,
+    /// and only while the event is running. Checked-in tickets can no longer be transferred or refunded.
+    pub fn check_in(env: Env, scanner: Address, event_id: u32, token_id: u128) -> u64 {
+        let event = Self::get_event(env.clone(), event_id);
+        Self::require_role(&env, event_id, &event, &scanner, Role::Scanner);
+        Self::redeem_ticket(&env, &event, event_id, &scanner, token_id)
+    }
+
+    /// Register the ed25519 public key used to sign offline check-in payloads
+    pub fn register_checkin_key(env: Env, holder: Address, public_key: BytesN<32>) {
+        holder.require_auth();
+
+        let key = DataKey::CheckinKey(holder.clone());
+        env.storage().persistent().set(&key, &public_key);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
+
+    /// Get the check-in public key registered by a holder
+    pub fn get_checkin_key(env: Env, holder: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::CheckinKey(holder))
+    }
+
+    /// Redeem a batch of check-ins signed offline by ticket holders. Each signature is
+    /// verified against the key registered by the ticket's current owner, and each nonce
+    /// can only be used once per holder. Only the organizer and scanners can submit
+    /// check-ins. The whole batch fails if any check-in is invalid.
+    pub fn redeem_signed_checkins(
+        env: Env,
+        scanner: Address,
+        event_id: u32,
+        checkins: Vec<SignedCheckin>,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+        Self::require_role(&env, event_id, &event, &scanner, Role::Scanner);
+
+        let current_time = env.ledger().timestamp();
+
+        for checkin in checkins.iter() {
+            if checkin.expires_at < current_time {
+                panic!("Check-in payload expired");
+            }
+
+            let holder: Address = env.invoke_contract(
+                &event.ticket_nft_addr,
+                &Symbol::new(&env, "owner_of"),
+                soroban_sdk::vec![&env, checkin.token_id.into_val(&env)],
+            );
+            let public_key = Self::get_checkin_key(env.clone(), holder.clone())
+                .unwrap_or_else(|| panic!("No check-in key registered"));
+
+            let nonce_key = DataKey::CheckinNonce(holder, checkin.nonce);
+            if env.storage().persistent().has(&nonce_key) {
+                panic!("Check-in nonce already used");
+            }
+
+            // Panics if the signature doesn't match
+            let payload = (
+                env.current_contract_address(),
+                event_id,
+                checkin.token_id,
+                checkin.expires_at,
+                checkin.nonce,
+            )
+                .to_xdr(&env);
+            env.crypto()
+                .ed25519_verify(&public_key, &payload, &checkin.signature);
+
+            env.storage().persistent().set(&nonce_key, &true);
+            env.storage().persistent().extend_ttl(
+                &nonce_key,
+                30 * 24 * 60 * 60 / 5,
+                100 * 24 * 60 * 60 / 5,
+            );
+
+            Self::redeem_ticket(&env, &event, event_id, &scanner, checkin.token_id);
+        }
+    }
+
+    /// Check whether a ticket of an event has been checked in
+    pub fn is_checked_in(env: Env, event_id: u32, token_id: u128) -> bool {
+        let event = Self::get_event(env.clone(), event_id);
+        env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "is_checked_in"),
+            soroban_sdk::vec![&env, token_id.into_val(&env)],
+        )
+    }
+
+    /// Set the royalty and optional price cap for resales of an event's tickets.
+    /// Only the organizer or a co-organizer can change them.
+    pub fn set_resale_policy(
+        env: Env,
+        caller: Address,
+        event_id: u32,
+        royalty_bps: u32,
+        max_price_bps: Option<u32>,
+    ) {
+        let event = Self::get_event(env.clone(), event_id);
+
+        // Only organizer or co-organizers can change the resale policy
+        Self::require_role(&env, event_id, &event, &caller, Role::CoOrganizer);
+
+        if royalty_bps > BPS_DENOMINATOR as u32 {
+            panic!("Royalty exceeds ticket price");
+        }
+        if max_price_bps == Some(0) {
+            panic!("Price cap must be greater than 0");
+        }
+
+        let key = DataKey::ResalePolicy(event_id);
+        env.storage().persistent().set(
+            &key,
+            &ResalePolicy {
+                royalty_bps,
+                max_price_bps,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events().publish(
+            (Symbol::new(&env, "resale_policy_updated"),),
+            (event_id, royalty_bps, max_price_bps),
+        );
+    }
+
+    /// Get the resale policy of an event. Resales are uncapped and royalty-free by default.
+    pub fn get_resale_policy(env: Env, event_id: u32) -> ResalePolicy {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ResalePolicy(event_id))
+            .unwrap_or(ResalePolicy {
+                royalty_bps: 0,
+                max_price_bps: None,
+            })
+    }
+
+    /// List a ticket for resale. The price can't exceed the event's resale cap, applied
+    /// to what was originally paid for the ticket, and the event manager is approved to
+    /// transfer the ticket to the eventual buyer.
+    pub fn list_ticket(env: Env, seller: Address, event_id: u32, token_id: u128, price: i128) {
+        seller.require_auth();
+
+        let event = Self::get_event(env.clone(), event_id);
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+        if price < 0 {
+            panic!("Price cannot be negative");
+        }
+
+        let owner: Address = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "owner_of"),
+            soroban_sdk::vec![&env, token_id.into_val(&env)],
+        );
+        if owner != seller {
+            panic!("Only the ticket owner can list it");
+        }
+        if Self::is_checked_in(env.clone(), event_id, token_id) {
+            panic!("Ticket already checked in");
+        }
+
+        Self::require_within_resale_cap(&env, event_id, token_id, price);
+
+        // Let the event manager deliver the ticket when it sells
+        env.invoke_contract::<()>(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "approve"),
+            soroban_sdk::vec![
+                &env,
+                seller.into_val(&env),
+                Some(env.current_contract_address()).into_val(&env),
+                token_id.into_val(&env),
+            ],
+        );
+
+        let key = DataKey::Listing(event_id, token_id);
+        env.storage().persistent().set(
+            &key,
+            &Listing {
+                seller: seller.clone(),
+                price,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events().publish(
+            (Symbol::new(&env, "ticket_listed"),),
+            (event_id, token_id, seller, price),
+        );
+    }
+
+    /// Withdraw a resale listing. Only the seller can cancel it.
+    pub fn cancel_listing(env: Env, event_id: u32, token_id: u128) {
+        let listing: Listing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(event_id, token_id))
+            .unwrap_or_else(|| panic!("Listing not found"));
+        listing.seller.require_auth();
+
+        Self::remove_listing(&env, event_id, token_id);
+    }
+
+    /// Get the active resale listing of a ticket. Listings stop being active once the
+    /// ticket is transferred, refunded or checked in.
+    pub fn get_listing(env: Env, event_id: u32, token_id: u128) -> Option<Listing> {
+        let listing: Listing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(event_id, token_id))?;
+        let event = Self::get_event(env.clone(), event_id);
+
+        let is_valid: bool = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "is_valid"),
+            soroban_sdk::vec![&env, token_id.into_val(&env)],
+        );
+        if !is_valid || Self::is_checked_in(env.clone(), event_id, token_id) {
+            return None;
+        }
+
+        // A transfer clears the approval, so this also catches moved tickets
+        let owner: Address = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "owner_of"),
+            soroban_sdk::vec![&env, token_id.into_val(&env)],
+        );
+        let approved: Option<Address> = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "get_approved"),
+            soroban_sdk::vec![&env, token_id.into_val(&env)],
+        );
+        if owner != listing.seller || approved != Some(env.current_contract_address()) {
+            return None;
+        }
+
+        Some(listing)
+    }
+
+    /// Buy a listed ticket. The buyer pays the listing price, the organizer receives the
+    /// royalty, the seller receives the rest, and the ticket moves to the buyer.
+    pub fn buy_listed_ticket(env: Env, buyer: Address, event_id: u32, token_id: u128) {
+        buyer.require_auth();
+
+        let event = Self::get_event(env.clone(), event_id);
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+
+        let listing = Self::get_listing(env.clone(), event_id, token_id)
+            .unwrap_or_else(|| panic!("Listing not found"));
+        Self::remove_listing(&env, event_id, token_id);
+
+        // The resale policy may have changed since the ticket was listed
+        Self::require_within_resale_cap(&env, event_id, token_id, listing.price);
+
+        // Pay the organizer's royalty and the seller
+        let royalty_bps = Self::get_resale_policy(env.clone(), event_id).royalty_bps;
+        let royalty = listing.price * royalty_bps as i128 / BPS_DENOMINATOR;
+        let token_client = token::Client::new(&env, &event.payment_token);
+        if royalty > 0 {
+            token_client.transfer(&buyer, &event.organizer, &royalty);
+        }
+        if listing.price - royalty > 0 {
+            token_client.transfer(&buyer, &listing.seller, &(listing.price - royalty));
+        }
+
+        env.invoke_contract::<()>(
+            &event.ticket_nft_addr,
+            &Symbol::new(&env, "transfer_from"),
+            soroban_sdk::vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                listing.seller.into_val(&env),
+                buyer.into_val(&env),
+                token_id.into_val(&env),
+            ],
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "ticket_resold"),),
+            (
+                event_id,
+                token_id,
+                listing.seller,
+                buyer,
+                listing.price,
+                royalty,
+            ),
+        );
+    }
+
+    // ========== Helper Functions ==========
+
+    /// Enforce the resale price cap relative to the ticket's face value, which is what
+    /// was paid for it including the platform fee
+    fn require_within_resale_cap(env: &Env, event_id: u32, token_id: u128, price: i128) {
+        let Some(max_price_bps) = Self::get_resale_policy(env.clone(), event_id).max_price_bps
+        else {
+            return;
+        };
+
+        let face_value = Self::get_ticket_payment(env.clone(), event_id, token_id)
+            .unwrap_or_else(|| panic!("Ticket not purchased through this event"));
+        if price > face_value * max_price_bps as i128 / BPS_DENOMINATOR {
+            panic!("Price exceeds resale cap");
+        }
+    }
+
+    /// `base_price * (1 + growth_bps / 10000) ^ sold`, capped at the curve's max price
+    fn exponential_price(curve: &ExponentialCurve, sold: u128) -> i128 {
+        if curve.base_price == 0 {
+            return 0;
+        }
+
+        // Compound the growth factor by squaring. Overflowing means the price is far
+        // past the cap.
+        let mut factor = CURVE_SCALE + curve.growth_bps as i128 * CURVE_SCALE / BPS_DENOMINATOR;
+        let mut multiplier = CURVE_SCALE;
+        let mut remaining = sold;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                match multiplier.checked_mul(factor) {
+                    Some(value) => multiplier = value / CURVE_SCALE,
+                    None => return curve.max_price,
+                }
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                match factor.checked_mul(factor) {
+                    Some(value) => factor = value / CURVE_SCALE,
+                    None => return curve.max_price,
+                }
+            }
+        }
+
+        curve
+            .base_price
+            .checked_mul(multiplier)
+            .map_or(curve.max_price, |price| {
+                (price / CURVE_SCALE).min(curve.max_price)
+            })
+    }
+
+    fn save_lottery_state(env: &Env, event_id: u32, state: &LotteryState) {
+        let key = DataKey::LotteryState(event_id);
+        env.storage().persistent().set(&key, state);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
+
+    fn save_auction_state(env: &Env, event_id: u32, state: &AuctionState) {
+        let key = DataKey::AuctionState(event_id);
+        env.storage().persistent().set(&key, state);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
+
+    /// Platform fee taken from a ticket sold at `price`
+    fn fee_per_ticket(fee: &Option<PlatformFee>, price: i128) -> i128 {
+        fee.as_ref().map_or(0, |fee| {
+            (price * fee.fee_bps as i128 / BPS_DENOMINATOR + fee.flat_fee).min(price)
+        })
+    }
+
+    /// Revenue splits can't change once tickets have been sold
+    fn lock_revenue_splits(env: &Env, event_id: u32) {
+        let splits_locked_key = DataKey::SplitsLocked(event_id);
+        if !env.storage().persistent().has(&splits_locked_key) {
+            env.storage().persistent().set(&splits_locked_key, &true);
+            env.storage().persistent().extend_ttl(
+                &splits_locked_key,
+                30 * 24 * 60 * 60 / 5,
+                100 * 24 * 60 * 60 / 5,
+            );
+        }
+    }
+
+    /// Apply a promo code to the unit prices of a purchase, using it once per ticket
+    fn redeem_promo_code(
+        env: &Env,
+        event_id: u32,
+        tier_id: u32,
+        buyer: &Address,
+        code: &Bytes,
+        prices: Vec<i128>,
+    ) -> Vec<i128> {
+        let code_hash: BytesN<32> = env.crypto().sha256(code).into();
+        let key = DataKey::PromoCode(event_id, code_hash.clone());
+        let mut promo: PromoCode = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Invalid promo code"));
+
+        if promo
+            .expires_at
+            .is_some_and(|expires_at| env.ledger().timestamp() >= expires_at)
+        {
+            panic!("Promo code expired");
+        }
+        if promo.tier_id.is_some_and(|id| id != tier_id) {
+            panic!("Promo code is not valid for this tier");
+        }
+        if prices.len() > promo.remaining_uses {
+            panic!("Promo code has no uses left");
+        }
+
+        let mut discounted = Vec::new(env);
+        let mut total_discount = 0;
+        for price in prices.iter() {
+            let discount = match promo.discount {
+                Discount::Percentage(bps) => price * bps as i128 / BPS_DENOMINATOR,
+                Discount::Fixed(amount) => amount.min(price),
+            };
+            total_discount += discount;
+            discounted.push_back(price - discount);
+        }
+
+        promo.remaining_uses -= prices.len();
+        env.storage().persistent().set(&key, &promo);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+
+        env.events().publish(
+            (Symbol::new(env, "promo_code_redeemed"), event_id),
+            (code_hash, buyer.clone(), prices.len(), total_discount),
+        );
+
+        discounted
+    }
+
+    /// Unit prices a buyer pays for the next `quantity` tickets in a tier. During a
+    /// presale only buyers with a valid allowlist proof can purchase.
+    fn sale_prices(
+        env: &Env,
+        event: &Event,
+        tier: &TicketTier,
+        buyer: &Address,
+        proof: &Option<Vec<BytesN<32>>>,
+        quantity: u128,
+    ) -> Vec<i128> {
+        Self::check_presale_access(env, event.id, buyer, proof);
+
+        let mut prices = Vec::new(env);
+        for i in 0..quantity {
+            let price = if tier.id == 0 {
+                Self::general_admission_price(env, event.id, event.tickets_sold + i)
+            } else {
+                tier.price
+            };
+            prices.push_back(price);
+        }
+        prices
+    }
+
+    fn check_presale_access(
+        env: &Env,
+        event_id: u32,
+        buyer: &Address,
+        proof: &Option<Vec<BytesN<32>>>,
+    ) {
+        let Some(presale) = Self::get_presale(env.clone(), event_id) else {
+            return;
+        };
+
+        let current_time = env.ledger().timestamp();
+        if current_time >= presale.public_sale_start {
+            return;
+        }
+        if current_time < presale.start || current_time >= presale.end {
+            panic!("Sale has not started");
+        }
+
+        let valid = proof
+            .as_ref()
+            .is_some_and(|proof| Self::verify_allowlist(env, &presale.merkle_root, buyer, proof));
+        if !valid {
+            panic!("Not on the presale allowlist");
+        }
+    }
+
+    /// Check a merkle proof that `buyer` is a leaf of the tree with the given root
+    fn verify_allowlist(
+        env: &Env,
+        root: &BytesN<32>,
+        buyer: &Address,
+        proof: &Vec<BytesN<32>>,
+    ) -> bool {
+        let mut node: BytesN<32> = env.crypto().sha256(&buyer.clone().to_xdr(env)).into();
+
+        for sibling in proof.iter() {
+            let mut pair = Bytes::new(env);
+            if node < sibling {
+                pair.append(&node.into());
+                pair.append(&sibling.into());
+            } else {
+                pair.append(&sibling.into());
+                pair.append(&node.into());
+            }
+            node = env.crypto().sha256(&pair).into();
+        }
+
+        node == *root
+    }
+
+    /// Tickets of a tier that can be sold outside the waitlist. Seats offered to
+    /// waitlisted users are held for them until the offer is claimed or expires.
+    fn public_availability(env: &Env, event: &Event, tier_id: u32) -> u128 {
+        let waitlist = Self::get_waitlist(env, event.id);
+        let event_free = event
+            .total_tickets
+            .saturating_sub(event.tickets_sold)
+            .saturating_sub(Self::total_offers(&waitlist) as u128);
+        let tier = Self::get_ticket_tier(env.clone(), event.id, tier_id);
+        let tier_free = tier
+            .total_supply
+            .saturating_sub(tier.sold)
+            .saturating_sub(waitlist.offers.get(tier_id).unwrap_or(0) as u128);
+        event_free.min(tier_free)
+    }
+
+    fn ensure_public_availability(env: &Env, event_id: u32, tier_id: u32, quantity: u32) {
+        if Self::get_waitlist(env, event_id).length == 0 {
+            return;
+        }
+
+        Self::advance_waitlist(env, event_id);
+        let event = Self::get_event(env.clone(), event_id);
+        let available = Self::public_availability(env, &event, tier_id);
+        if available == 0 {
+            panic!("Event is sold out");
+        }
+        if quantity as u128 > available {
+            panic!("Not enough tickets available");
+        }
+    }
+
+    fn get_waitlist(env: &Env, event_id: u32) -> Waitlist {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Waitlist(event_id))
+            .unwrap_or_else(|| Waitlist {
+                head: 0,
+                tail: 0,
+                length: 0,
+                offers: Map::new(env),
+            })
+    }
+
+    fn total_offers(waitlist: &Waitlist) -> u32 {
+        waitlist.offers.values().iter().sum()
+    }
+
+    fn save_waitlist(env: &Env, event_id: u32, waitlist: &Waitlist) {
+        let key = DataKey::Waitlist(event_id);
+        env.storage().persistent().set(&key, waitlist);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
+
+    fn save_waitlist_entry(env: &Env, event_id: u32, index: u32, entry: &WaitlistEntry) {
+        let key = DataKey::WaitlistEntry(event_id, index);
+        env.storage().persistent().set(&key, entry);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
+
+    /// Drop a waitlist entry and return its deposit
+    fn remove_waitlist_entry(env: &Env, event_id: u32, index: u32) {
+        let entry: WaitlistEntry = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WaitlistEntry(event_id, index))
+            .unwrap();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WaitlistEntry(event_id, index));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WaitlistIndex(event_id, entry.user.clone()));
+
+        let mut waitlist = Self::get_waitlist(env, event_id);
+        waitlist.length -= 1;
+        if entry.offer_expires_at.is_some() {
+            let offers = waitlist.offers.get(entry.tier_id).unwrap_or(0);
+            waitlist.offers.set(entry.tier_id, offers - 1);
+        }
+        Self::save_waitlist(env, event_id, &waitlist);
+
+        if entry.deposit > 0 {
+            let event = Self::get_event(env.clone(), event_id);
+            let token_client = token::Client::new(env, &event.payment_token);
+            token_client.transfer(&env.current_contract_address(), &entry.user, &entry.deposit);
+        }
+    }
+
+    /// Drop expired offers and offer every free seat to the next users in line who
+    /// are waiting for its tier
+    fn advance_waitlist(env: &Env, event_id: u32) {
+        let mut waitlist = Self::get_waitlist(env, event_id);
+        if waitlist.length == 0 {
+            return;
+        }
+
+        let current_time = env.ledger().timestamp();
+
+        // Offers are only held by entries, so stop once all of them have been seen
+        let mut pending = Self::total_offers(&waitlist);
+        let mut index = waitlist.head;
+        while index < waitlist.tail && pending > 0 {
+            let key = DataKey::WaitlistEntry(event_id, index);
+            if let Some(entry) = env.storage().persistent().get::<_, WaitlistEntry>(&key) {
+                if let Some(expires_at) = entry.offer_expires_at {
+                    pending -= 1;
+                    if current_time > expires_at {
+                        Self::remove_waitlist_entry(env, event_id, index);
+                        env.events().publish(
+                            (Symbol::new(env, "waitlist_offer_expired"),),
+                            (event_id, entry.user),
+                        );
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        let event = Self::get_event(env.clone(), event_id);
+        waitlist = Self::get_waitlist(env, event_id);
+        let mut free = event
+            .total_tickets
+            .saturating_sub(event.tickets_sold)
+            .saturating_sub(Self::total_offers(&waitlist) as u128);
+
+        let mut index = waitlist.head;
+        while index < waitlist.tail && free > 0 {
+            let key = DataKey::WaitlistEntry(event_id, index);
+            let Some(mut entry) = env.storage().persistent().get::<_, WaitlistEntry>(&key) else {
+                index += 1;
+                continue;
+            };
+
+            let offers = waitlist.offers.get(entry.tier_id).unwrap_or(0);
+            let tier = Self::get_ticket_tier(env.clone(), event_id, entry.tier_id);
+            if entry.offer_expires_at.is_none() && tier.sold + (offers as u128) < tier.total_supply
+            {
+                let expires_at = current_time + WAITLIST_CLAIM_WINDOW;
+                entry.offer_expires_at = Some(expires_at);
+                Self::save_waitlist_entry(env, event_id, index, &entry);
+                waitlist.offers.set(entry.tier_id, offers + 1);
+                env.events().publish(
+                    (Symbol::new(env, "waitlist_offer"),),
+                    (event_id, entry.user, expires_at),
+                );
+                free -= 1;
+            }
+            index += 1;
+        }
+
+        // Skip past entries that have left the queue
+        while waitlist.head < waitlist.tail
+            && !env
+                .storage()
+                .persistent()
+                .has(&DataKey::WaitlistEntry(event_id, waitlist.head))
+        {
+            waitlist.head += 1;
+        }
+        Self::save_waitlist(env, event_id, &waitlist);
+    }
+
+    /// Free a ticket's seat in its tier and burn it
+    fn release_seat(env: &Env, event_id: u32, event: &mut Event, token_id: u128) {
+        let tier_id: u32 = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(env, "tier_of"),
+            soroban_sdk::vec![env, token_id.into_val(env)],
+        );
+        Self::free_seat(env, event_id, event, tier_id);
+
+        Self::remove_listing(env, event_id, token_id);
+        env.invoke_contract::<()>(
+            &event.ticket_nft_addr,
+            &Symbol::new(env, "burn"),
+            soroban_sdk::vec![env, token_id.into_val(env)],
+        );
+    }
+
+    /// Return a sold or reserved seat in a tier to the pool
+    fn free_seat(env: &Env, event_id: u32, event: &mut Event, tier_id: u32) {
+        let mut tier = Self::get_ticket_tier(env.clone(), event_id, tier_id);
+        tier.sold -= 1;
+        Self::save_tier(env, event_id, &tier);
+        event.tickets_sold -= 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(event_id), event);
+    }
+
+    /// Check whether a user can hold another of the event's tickets
+    fn has_wallet_room(env: &Env, event: &Event, user: &Address) -> bool {
+        let balance: u128 = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(env, "balance_of"),
+            soroban_sdk::vec![env, user.into_val(env)],
+        );
+        let limit: u32 = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(env, "max_tickets_per_wallet"),
+            Vec::new(env),
+        );
+        balance < limit as u128
+    }
+
+    fn remove_listing(env: &Env, event_id: u32, token_id: u128) {
+        let key = DataKey::Listing(event_id, token_id);
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().remove(&key);
+            env.events().publish(
+                (Symbol::new(env, "listing_canceled"),),
+                (event_id, token_id),
+            );
+        }
+    }
+
+    fn is_revenue_withdrawn(env: &Env, event_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RevenueWithdrawn(event_id))
+            .unwrap_or(false)
+    }
+
+    fn mark_revenue_withdrawn(env: &Env, event_id: u32) {
+        let key = DataKey::RevenueWithdrawn(event_id);
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
+
+    /// Revenue can only be paid out once an event has ended, and never for a canceled event
+    fn require_settleable(env: &Env, event: &Event) {
+        // Funds of a canceled event are reserved for refunds
+        if event.is_canceled {
+            panic!("Cannot withdraw funds for a canceled event");
+        }
+
+        if env.ledger().timestamp() < event.end_date {
+            panic!("Event has not ended yet");
+        }
+    }
+
+    /// Pay a payee whatever part of their share of the gross revenue hasn't been paid yet
+    fn pay_revenue_share(env: &Env, event_id: u32, event: &Event, split: &RevenueSplit) -> i128 {
+        let paid_total: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SplitPaidTotal(event_id))
+            .unwrap_or(0);
+        let gross = Self::get_event_balance(env.clone(), event_id) + paid_total;
+
+        let paid = Self::get_revenue_paid(env.clone(), event_id, split.payee.clone());
+        let amount = gross * split.bps as i128 / BPS_DENOMINATOR - paid;
+        if amount <= 0 {
+            return 0;
+        }
+
+        // Record the payout before transferring
+        let paid_key = DataKey::SplitPaid(event_id, split.payee.clone());
+        env.storage().persistent().set(&paid_key, &(paid + amount));
+        env.storage().persistent().extend_ttl(
+            &paid_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+        let paid_total_key = DataKey::SplitPaidTotal(event_id);
+        env.storage()
+            .persistent()
+            .set(&paid_total_key, &(paid_total + amount));
+        env.storage().persistent().extend_ttl(
+            &paid_total_key,
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+        Self::adjust_balance(env, event_id, -amount);
+        Self::mark_revenue_withdrawn(env, event_id);
+
+        let token_client = token::Client::new(env, &event.payment_token);
+        token_client.transfer(&env.current_contract_address(), &split.payee, &amount);
+
+        env.events().publish(
+            (Symbol::new(env, "revenue_share_paid"),),
+            (event_id, split.payee.clone(), amount),
+        );
+
+        amount
+    }
+
+    /// Require `caller` to be the event's organizer or to hold `role` for the event
+    fn require_role(env: &Env, event_id: u32, event: &Event, caller: &Address, role: Role) {
+        caller.require_auth();
+
+        if *caller != event.organizer
+            && !Self::has_role(env.clone(), event_id, caller.clone(), role)
+        {
+            panic!("Unauthorized");
+        }
+    }
+
+    fn redeem_ticket(
+        env: &Env,
+        event: &Event,
+        event_id: u32,
+        scanner: &Address,
+        token_id: u128,
+    ) -> u64 {
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time < event.start_date || current_time > event.end_date {
+            panic!("Check-in is only allowed during the event");
+        }
+
+        if Self::is_checked_in(env.clone(), event_id, token_id) {
+            panic!("Ticket already checked in");
+        }
+
+        let checked_in_at: u64 = env.invoke_contract(
+            &event.ticket_nft_addr,
+            &Symbol::new(env, "check_in"),
+            soroban_sdk::vec![env, token_id.into_val(env)],
+        );
+        Self::remove_listing(env, event_id, token_id);
+
+        // Emit check-in event
+        env.events().publish(
+            (Symbol::new(env, "checked_in"),),
+            (event_id, token_id, scanner.clone(), checked_in_at),
+        );
+
+        checked_in_at
+    }
+
+    fn validate_event_params(
+        env: &Env,
+        start_date: u64,
+        end_date: u64,
+        ticket_price: i128,
+        total_tickets: u128,
+    ) {
+        let current_time = env.ledger().timestamp();
+
+        // Validate dates
+        if start_date < current_time {
+            panic!("Start date must be in the future");
+        }
+
+        if end_date <= start_date {
+            panic!("End date must be after start date");
+        }
+
+        // Validate ticket price
+        if ticket_price < 0 {
+            panic!("Ticket price cannot be negative");
+        }
+
+        // Validate total tickets
+        if total_tickets == 0 {
+            panic!("Total tickets must be greater than 0");
+        }
+    }
+
+    fn sell_tickets(
+        env: &Env,
+        buyer: &Address,
+        event_id: u32,
+        tier_id: u32,
+        recipients: &Vec<Address>,
+        options: &PurchaseOptions,
+    ) -> Vec<u128> {
+        let mut event = Self::get_event(env.clone(), event_id);
+
+        if event.is_canceled {
+            panic!("Event is canceled");
+        }
+
+        let quantity = recipients.len() as u128;
+
+        if event.tickets_sold >= event.total_tickets {
+            panic!("Event is sold out");
+        }
+        if event.tickets_sold + quantity > event.total_tickets {
+            panic!("Not enough tickets available");
+        }
+
+        let mut tier = Self::get_ticket_tier(env.clone(), event_id, tier_id);
+
+        if Self::get_dutch_auction(env.clone(), event_id)
+            .is_some_and(|auction| auction.tier_id == tier_id)
+        {
+            panic!("Tier is sold by auction");
+        }
+        if Self::get_lottery(env.clone(), event_id)
+            .is_some_and(|lottery| lottery.tier_id == tier_id)
+        {
+            panic!("Tier is sold by lottery");
+        }
+        if tier.sold >= tier.total_supply {
+            panic!("Tier is sold out");
+        }
+        if tier.sold + quantity > tier.total_supply {
+            panic!("Not enough tickets available");
         }
 
         let current_time = env.ledger().timestamp();
-
-        // Apply theme if provided
-        if let Some(t) = theme {
-            event.theme = t;
+        if tier.sale_start.is_some_and(|start| current_time < start) {
+            panic!("Tier sale has not started");
         }
-
-        // Apply ticket_price if provided (must be non-negative)
-        if let Some(p) = ticket_price {
-            if p < 0 {
-                panic!("Ticket price cannot be negative");
-            }
-            event.ticket_price = p;
+        if tier.sale_end.is_some_and(|end| current_time >= end) {
+            panic!("Tier sale has ended");
         }
-
-        // Apply total_tickets if provided (cannot be below tickets_sold)
-        if let Some(t) = total_tickets {
-            if t == 0 {
-                panic!("Total tickets must be greater than 0");
-            }
-            if t < event.tickets_sold {
-                panic!("Cannot reduce total_tickets below tickets_sold");
-            }
-            event.total_tickets = t;
+        let mut prices = Self::sale_prices(env, &event, &tier, buyer, &options.proof, quantity);
+        if let Some(code) = &options.promo_code {
+            prices = Self::redeem_promo_code(env, event_id, tier_id, buyer, code, prices);
+        }
+        let total_price: i128 = prices.iter().sum();
+        if options
+            .max_price
+            .is_some_and(|max_price| total_price > max_price)
+        {
+            panic!("Price exceeds max price");
         }
 
-        // Effective end for start validation (new end if provided in this call, else current)
-        let effective_end = end_date.unwrap_or(event.end_date);
-        // Apply start_date if provided
-        if let Some(s) = start_date {
-            if s < current_time {
-                panic!("Start date cannot be in the past");
-            }
-            if s >= effective_end {
-                panic!("Start date must be before end date");
-            }
-            event.start_date = s;
+        // Split the platform fee from each ticket price
+        let fee = Self::get_event_fee(env.clone(), event_id);
+        let total_fee: i128 = prices
+            .iter()
+            .map(|price| Self::fee_per_ticket(&fee, price))
+            .sum();
+
+        // Handle payment, charging the buyer once for all seats. The platform fee stays in
+        // escrow with the revenue until the event settles.
+        if total_price > 0 {
+            let token_client = token::Client::new(env, &event.payment_token);
+            token_client.transfer(buyer, &env.current_contract_address(), &total_price);
+            Self::adjust_balance(env, event_id, total_price - total_fee);
+            Self::adjust_fees(env, event_id, total_fee);
         }
 
-        // Effective start for end validation (new start if provided in this call, else current)
-        let effective_start = start_date.unwrap_or(event.start_date);
-        // Apply end_date if provided
-        if let Some(e) = end_date {
-            if e < current_time {
-                panic!("End date cannot be in the past");
-            }
-            if e <= effective_start {
-                panic!("End date must be after start date");
-            }
-            event.end_date = e;
+        let mut token_ids = Vec::new(env);
+        for (recipient, price) in recipients.iter().zip(prices.iter()) {
+            // Mint ticket NFT, recording the tier on the token
+            let token_id = env.invoke_contract::<u128>(
+                &event.ticket_nft_addr,
+                &Symbol::new(env, "mint_ticket_nft"),
+                soroban_sdk::vec![env, recipient.into_val(env), tier_id.into_val(env)],
+            );
+
+            // Record what was paid so refunds don't depend on the current price
+            let payment_key = DataKey::TicketPayment(event_id, token_id);
+            env.storage().persistent().set(&payment_key, &price);
+            env.storage().persistent().extend_ttl(
+                &payment_key,
+                30 * 24 * 60 * 60 / 5,
+                100 * 24 * 60 * 60 / 5,
+            );
+
+            let purchased_at_key = DataKey::TicketPurchasedAt(event_id, token_id);
+            env.storage()
+                .persistent()
+                .set(&purchased_at_key, &current_time);
+            env.storage().persistent().extend_ttl(
+                &purchased_at_key,
+                30 * 24 * 60 * 60 / 5,
+                100 * 24 * 60 * 60 / 5,
+            );
+
+            // Emit purchase event
+            env.events().publish(
+                (Symbol::new(env, "ticket_purchased"),),
+                (event_id, recipient, event.ticket_nft_addr.clone()),
+            );
+
+            token_ids.push_back(token_id);
         }
 
-        // Update storage
+        Self::lock_revenue_splits(env, event_id);
+
+        // Update tickets sold
+        tier.sold += quantity;
+        Self::save_tier(env, event_id, &tier);
+        event.tickets_sold += quantity;
+
+        // Store updated event
         env.storage()
             .persistent()
             .set(&DataKey::Event(event_id), &event);
@@ -282,128 +3821,270 @@ impl EventManager {
             100 * 24 * 60 * 60 / 5,
         );
 
-        // Emit event_updated event
-        env.events()
-            .publish((Symbol::new(&env, "event_updated"),), (event_id, event.organizer.clone()));
+        token_ids
     }
 
-    /// Update tickets sold (called by ticket purchase logic)
-    pub fn update_tickets_sold(env: Env, event_id: u32, amount: u128) -> Result<(), Error> {
-        let mut event: Event = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Event(event_id))
-            .ok_or(Error::EventNotFound)?;
+    fn validate_tier_params(
+        price: i128,
+        total_supply: u128,
+        max_supply: u128,
+        sale_start: Option<u64>,
+        sale_end: Option<u64>,
+    ) {
+        if price < 0 {
+            panic!("Ticket price cannot be negative");
+        }
 
-        // Verify the caller (should be the ticket NFT contract or authorized entity)
-        event.ticket_nft_addr.require_auth();
+        if total_supply == 0 {
+            panic!("Tier supply must be greater than 0");
+        }
 
-        // Update tickets sold
-        event.tickets_sold = event
-            .tickets_sold
-            .checked_add(amount)
-            .ok_or(Error::CounterOverflow)?;
+        if total_supply > max_supply {
+            panic!("Tier supply cannot exceed total tickets");
+        }
 
-        // Ensure we don't oversell
-        if event.tickets_sold > event.total_tickets {
-            return Err(Error::CannotSellMoreTickets);
+        if let (Some(start), Some(end)) = (sale_start, sale_end) {
+            if end <= start {
+                panic!("Tier sale end must be after sale start");
+            }
         }
+    }
 
-        // Update storage
+    fn save_tier(env: &Env, event_id: u32, tier: &TicketTier) {
+        let key = DataKey::TicketTier(event_id, tier.id);
+        env.storage().persistent().set(&key, tier);
         env.storage()
             .persistent()
-            .set(&DataKey::Event(event_id), &event);
-        
-        Ok(())
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
     }
 
-    /// Purchase a ticket for an event
-    pub fn purchase_ticket(env: Env, buyer: Address, event_id: u32) {
-        buyer.require_auth();
-
-        let mut event: Event = env
+    fn in_reschedule_window(env: &Env, event_id: u32, token_id: u128) -> bool {
+        let window: Option<RescheduleWindow> = env
             .storage()
             .persistent()
-            .get(&DataKey::Event(event_id))
-            .unwrap_or_else(|| panic!("Event not found"));
+            .get(&DataKey::RescheduleWindow(event_id));
+        let purchased_at: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TicketPurchasedAt(event_id, token_id));
 
-        if event.is_canceled {
-            panic!("Event is canceled");
+        match (window, purchased_at) {
+            (Some(window), Some(purchased_at)) => {
+                env.ledger().timestamp() < window.refund_deadline
+                    && purchased_at <= window.rescheduled_at
+            }
+            _ => false,
         }
+    }
 
-        if event.tickets_sold >= event.total_tickets {
-            panic!("Event is sold out");
-        }
+    fn status_of(env: &Env, event: &Event) -> EventStatus {
+        let current_time = env.ledger().timestamp();
 
-        // Handle payment
-        if event.ticket_price > 0 {
-            let token_client = soroban_sdk::token::Client::new(&env, &event.payment_token);
-            token_client.transfer(&buyer, &event.organizer, &event.ticket_price);
+        if event.is_canceled {
+            EventStatus::Canceled
+        } else if current_time < event.start_date {
+            EventStatus::Upcoming
+        } else if current_time < event.end_date {
+            EventStatus::Active
+        } else {
+            EventStatus::Ended
         }
+    }
 
-        // Mint ticket NFT
-        env.invoke_contract::<u128>(
-            &event.ticket_nft_addr,
-            &Symbol::new(&env, "mint_ticket_nft"),
-            soroban_sdk::vec![&env, buyer.into_val(&env)],
-        );
-
-        // Update tickets sold
-        event.tickets_sold += 1;
+    fn push_index(
+        env: &Env,
+        count_key: DataKey,
+        entry_key: impl Fn(u32) -> DataKey,
+        event_id: u32,
+    ) {
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let key = entry_key(count);
 
-        // Store updated event
+        env.storage().persistent().set(&key, &event_id);
         env.storage()
             .persistent()
-            .set(&DataKey::Event(event_id), &event);
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
 
-        // Extend TTL
+        env.storage().persistent().set(&count_key, &(count + 1));
         env.storage().persistent().extend_ttl(
-            &DataKey::Event(event_id),
+            &count_key,
             30 * 24 * 60 * 60 / 5,
             100 * 24 * 60 * 60 / 5,
         );
+    }
 
-        // Emit purchase event
-        env.events().publish(
-            (Symbol::new(&env, "ticket_purchased"),),
-            (event_id, buyer, event.ticket_nft_addr),
-        );
+    fn date_index(env: &Env, key: &DataKey) -> Vec<(u64, u32)> {
+        env.storage()
+            .persistent()
+            .get(key)
+            .unwrap_or_else(|| Vec::new(env))
     }
 
-    // ========== Helper Functions ==========
+    fn save_date_index(env: &Env, key: &DataKey, index: &Vec<(u64, u32)>) {
+        if index.is_empty() {
+            env.storage().persistent().remove(key);
+            return;
+        }
+        env.storage().persistent().set(key, index);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
 
-    fn validate_event_params(
-        env: &Env,
-        start_date: u64,
-        end_date: u64,
-        ticket_price: i128,
-        total_tickets: u128,
-    ) -> Result<(), Error> {
-        let current_time = env.ledger().timestamp();
+    fn index_days(env: &Env, index: DateIndex) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DateBucketDays(index))
+            .unwrap_or_else(|| Vec::new(env))
+    }
 
-        // Validate dates
-        if start_date < current_time {
-            return Err(Error::InvalidStartDate);
+    fn save_index_days(env: &Env, index: DateIndex, days: &Vec<u64>) {
+        let key = DataKey::DateBucketDays(index);
+        env.storage().persistent().set(&key, days);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
+
+    /// Position of the first entry of a date bucket dated after `time`
+    fn first_after(index: &Vec<(u64, u32)>, time: u64) -> u32 {
+        let (mut low, mut high) = (0, index.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if index.get(mid).unwrap().0 <= time {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
         }
+        low
+    }
 
-        if end_date <= start_date {
-            return Err(Error::InvalidEndDate);
+    /// Position of the first day at or after `day`
+    fn first_day_from(days: &Vec<u64>, day: u64) -> u32 {
+        let (mut low, mut high) = (0, days.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if days.get(mid).unwrap() < day {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
         }
+        low
+    }
 
-        // Validate ticket price
-        if ticket_price < 0 {
-            return Err(Error::NegativeTicketPrice);
+    fn insert_dated(env: &Env, index: DateIndex, date: u64, event_id: u32) {
+        let day = date / DATE_BUCKET_SECONDS;
+        let key = DataKey::DateBucket(index, day);
+        let mut bucket = Self::date_index(env, &key);
+        if bucket.is_empty() {
+            let mut days = Self::index_days(env, index);
+            days.insert(Self::first_day_from(&days, day), day);
+            Self::save_index_days(env, index, &days);
         }
+        bucket.insert(Self::first_after(&bucket, date), (date, event_id));
+        Self::save_date_index(env, &key, &bucket);
+    }
 
-        // Validate total tickets
-        if total_tickets == 0 {
-            return Err(Error::InvalidTicketCount);
+    fn remove_dated(env: &Env, index: DateIndex, date: u64, event_id: u32) {
+        let day = date / DATE_BUCKET_SECONDS;
+        let key = DataKey::DateBucket(index, day);
+        let mut bucket = Self::date_index(env, &key);
+        let Some(position) = bucket.first_index_of((date, event_id)) else {
+            return;
+        };
+        bucket.remove(position);
+        Self::save_date_index(env, &key, &bucket);
+
+        if bucket.is_empty() {
+            let mut days = Self::index_days(env, index);
+            if let Some(position) = days.first_index_of(day) {
+                days.remove(position);
+                Self::save_index_days(env, index, &days);
+            }
+        }
+    }
+
+    /// Visit the entries of a date index in date order, starting from the bucket of
+    /// `from`, until `visit` returns false
+    fn scan_date_index(
+        env: &Env,
+        index: DateIndex,
+        from: u64,
+        mut visit: impl FnMut(u64, u32) -> bool,
+    ) {
+        let days = Self::index_days(env, index);
+        let first = Self::first_day_from(&days, from / DATE_BUCKET_SECONDS);
+        for day in days.slice(first..).iter() {
+            for (date, event_id) in Self::date_index(env, &DataKey::DateBucket(index, day)).iter() {
+                if !visit(date, event_id) {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn index_dates(env: &Env, event_id: u32, start_date: u64, end_date: u64) {
+        Self::archive_ended(env, ARCHIVE_BATCH);
+        Self::insert_dated(env, DateIndex::Start, start_date, event_id);
+        Self::insert_dated(env, DateIndex::End, end_date, event_id);
+    }
+
+    fn unindex_dates(env: &Env, event_id: u32, start_date: u64, end_date: u64) {
+        Self::remove_dated(env, DateIndex::Start, start_date, event_id);
+        Self::remove_dated(env, DateIndex::End, end_date, event_id);
+    }
+
+    /// Move up to `limit` ended events, earliest end first, from the date indexes to
+    /// the ended index
+    fn archive_ended(env: &Env, limit: u32) -> u32 {
+        let current_time = env.ledger().timestamp();
+        let mut ended = Vec::new(env);
+        Self::scan_date_index(env, DateIndex::End, 0, |end_date, event_id| {
+            if end_date > current_time || ended.len() >= limit {
+                return false;
+            }
+            ended.push_back((end_date, event_id));
+            true
+        });
+
+        for (end_date, event_id) in ended.iter() {
+            let event = Self::get_event(env.clone(), event_id);
+            Self::unindex_dates(env, event_id, event.start_date, end_date);
+            Self::push_index(
+                env,
+                DataKey::StatusEventCount(EventStatus::Ended),
+                |i| DataKey::StatusEvent(EventStatus::Ended, i),
+                event_id,
+            );
+        }
+
+        ended.len()
+    }
+
+    fn read_index_page(
+        env: &Env,
+        count_key: DataKey,
+        entry_key: impl Fn(u32) -> DataKey,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Event> {
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut events = Vec::new(env);
+
+        for i in start..end {
+            let event_id: u32 = env.storage().persistent().get(&entry_key(i)).unwrap();
+            if let Some(event) = env.storage().persistent().get(&DataKey::Event(event_id)) {
+                events.push_back(event);
+            }
         }
-        
-        Ok(())
+
+        events
     }
 
-    fn get_and_increment_counter(env: &Env) -> Result<u32, Error> {
+    fn get_and_increment_counter(env: &Env) -> u32 {
         let current: u32 = env
             .storage()
             .instance()
@@ -412,160 +4093,113 @@ impl EventManager {
 
         let next = current
             .checked_add(1)
-            .ok_or(Error::CounterOverflow)?;
+            .unwrap_or_else(|| panic!("Event counter overflow"));
 
         env.storage().instance().set(&DataKey::EventCounter, &next);
 
-        Ok(current)
+        current
     }
 
-    fn deploy_ticket_nft(env: &Env, event_id: u32, theme: String, total_supply: u128) -> Result<Address, Error> {
-        let factory_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::TicketFactory)
-            .ok_or(Error::FactoryNotInitialized)?;
+    fn adjust_balance(env: &Env, event_id: u32, delta: i128) {
+        let key = DataKey::EventBalance(event_id);
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
 
-        // Call the factory contract to deploy a new NFT contract
-            .unwrap_or_else(|| panic!("Ticket factory not initialized"));
-        // This is a cross-contract call
+        env.storage().persistent().set(&key, &(balance + delta));
 
-        Ok(nft_addr)
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, testutils::Ledger, vec, Env, Symbol};
-
-    #[contract]
-    pub struct MockFactory;
-
-    #[contractimpl]
-    impl MockFactory {
-        pub fn deploy_ticket_nft(
-            env: Env,
-            _event_id: u32,
-            _theme: String,
-            _total_supply: u128,
-        ) -> Address {
-            Address::generate(&env)
+    fn adjust_fees(env: &Env, event_id: u32, delta: i128) {
+        if delta == 0 {
+            return;
         }
-    }
 
-    #[test]
-    fn test_create_event() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, EventManager);
-        let client = EventManagerClient::new(&env, &contract_id);
+        let key = DataKey::EventFees(event_id);
+        let fees: i128 = env.storage().persistent().get(&key).unwrap_or(0);
 
-        let factory_addr = env.register_contract(None, MockFactory);
-        let organizer = Address::generate(&env);
+        env.storage().persistent().set(&key, &(fees + delta));
 
-        // Mock the organizer authorization
-        env.mock_all_auths();
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
+    }
 
-        // Initialize
-        client.initialize(&factory_addr).unwrap();
+    /// Pay an event's escrowed platform fees to the treasury
+    fn pay_platform_fees(env: &Env, event_id: u32, event: &Event) -> i128 {
+        let amount = Self::get_event_fees(env.clone(), event_id);
+        let Some(fee) = Self::get_event_fee(env.clone(), event_id).filter(|_| amount > 0) else {
+            return 0;
+        };
 
-        // Create event
-        let theme = String::from_str(&env, "Rust Conference 2026");
-        let event_type = String::from_str(&env, "Conference");
-        let start_date = env.ledger().timestamp() + 86400; // 1 day from now
-        let end_date = start_date + 86400; // 2 days from now
-        let ticket_price = 1000_0000000; // 100 XLM (7 decimals)
-        let total_tickets = 500;
+        // Clear the fees before paying out
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventFees(event_id), &0i128);
 
-        let event_id = client.create_event(
-            &organizer,
-            &theme,
-            &event_type,
-            &start_date,
-            &end_date,
-            &ticket_price,
-            &total_tickets,
-        ).unwrap();
+        let token_client = token::Client::new(env, &event.payment_token);
+        token_client.transfer(&env.current_contract_address(), &fee.treasury, &amount);
 
-        assert_eq!(event_id, 0);
+        env.events().publish(
+            (Symbol::new(env, "platform_fees_paid"),),
+            (event_id, fee.treasury, amount),
+        );
 
-        // Get event
-        let event = client.get_event(&event_id).unwrap();
-        assert_eq!(event.id, 0);
-        assert_eq!(event.organizer, organizer);
-        assert_eq!(event.total_tickets, total_tickets);
-        assert_eq!(event.tickets_sold, 0);
-        assert_eq!(event.is_canceled, false);
+        amount
     }
 
-    #[test]
-    fn test_create_event_past_date() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, EventManager);
-        let client = EventManagerClient::new(&env, &contract_id);
-
-        let factory_addr = env.register_contract(None, MockFactory);
-        let organizer = Address::generate(&env);
-
-        env.mock_all_auths();
-        env.ledger().set_timestamp(1000);
-        client.initialize(&factory_addr).unwrap();
-
-        let theme = String::from_str(&env, "Past Event");
-        let event_type = String::from_str(&env, "Conference");
-        let start_date = env.ledger().timestamp().saturating_sub(1); // Past date
-        let end_date = start_date.saturating_add(86400);
-
-        let result = client.create_event(
-            &organizer,
-            &theme,
-            &event_type,
-            &start_date,
-            &end_date,
-            &1000_0000000,
-            &100,
+    /// Cap the event's ticket contract at the event's total_tickets
+    fn sync_max_supply(env: &Env, event: &Event) {
+        env.invoke_contract::<()>(
+            &event.ticket_nft_addr,
+            &Symbol::new(env, "set_max_supply"),
+            soroban_sdk::vec![env, event.total_tickets.into_val(env)],
         );
-        
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), Error::InvalidStartDate);
     }
 
-    #[test]
-    fn test_cancel_event() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, EventManager);
-        let client = EventManagerClient::new(&env, &contract_id);
-
-        let factory_addr = env.register_contract(None, MockFactory);
-        let organizer = Address::generate(&env);
-
-        env.mock_all_auths();
-        client.initialize(&factory_addr).unwrap();
+    /// Align the ticket contract's transfer freeze with the event's current dates
+    fn sync_transfer_lock(env: &Env, event_id: u32, event: &Event) {
+        if let Some(window) = Self::get_transfer_lock_window(env.clone(), event_id) {
+            env.invoke_contract::<()>(
+                &event.ticket_nft_addr,
+                &Symbol::new(env, "set_transfer_lock"),
+                soroban_sdk::vec![
+                    env,
+                    event.start_date.saturating_sub(window).into_val(env),
+                    event.end_date.into_val(env),
+                ],
+            );
+        }
+    }
 
-        let event_id = client.create_event(
-            &organizer,
-            &String::from_str(&env, "Event"),
-            &String::from_str(&env, "Type"),
-            &(env.ledger().timestamp() + 86400),
-            &(env.ledger().timestamp() + 172800),
-            &1000_0000000,
-            &100,
-        ).unwrap();
+    fn deploy_ticket_nft(
+        env: &Env,
+        event_id: u32,
+        total_supply: u128,
+        transfer_policy: TransferPolicy,
+    ) -> Address {
+        let factory_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TicketFactory)
+            .unwrap_or_else(|| panic!("Ticket factory not initialized"));
 
-        client.cancel_event(&event_id).unwrap();
+        // Derive a unique salt per event so each NFT contract gets its own address
+        let mut salt_bytes = [0u8; 32];
+        salt_bytes[28..].copy_from_slice(&event_id.to_be_bytes());
+        let salt = BytesN::from_array(env, &salt_bytes);
 
-        let event = client.get_event(&event_id).unwrap();
-        assert_eq!(event.is_canceled, true);
-    }
-}
-        let salt = BytesN::from_array(&env, &[0u8; 32]);
-        let mut args = Vec::new(&env);
+        // Call the factory contract to deploy a new NFT contract
+        // This is a cross-contract call
+        let mut args = Vec::new(env);
         args.push_back(env.current_contract_address().to_val());
         args.push_back(salt.to_val());
+        args.push_back(DEFAULT_MAX_TICKETS_PER_WALLET.into_val(env));
+        args.push_back(total_supply.into_val(env));
+        args.push_back(transfer_policy.into_val(env));
 
-        let nft_addr: Address =
-            env.invoke_contract(&factory_addr, &Symbol::new(&env, "deploy_ticket"), args);
-        nft_addr
+        env.invoke_contract(&factory_addr, &Symbol::new(env, "deploy_ticket"), args)
     }
 }
 
@@ -581,7 +4215,7 @@ mod update_event_tests {
         let mock_addr = env.register(MockContract, ());
         let organizer = Address::generate(env);
         env.mock_all_auths();
-        client.initialize(&mock_addr);
+        client.initialize(&mock_addr, &Address::generate(env));
 
         let start_date = env.ledger().timestamp() + 86400;
         let end_date = start_date + 86400;
@@ -594,6 +4228,7 @@ mod update_event_tests {
             &1000_0000000,
             &100,
             &Address::generate(env),
+            &TransferPolicy::Free,
         );
         (client, organizer, event_id)
     }
@@ -601,9 +4236,10 @@ mod update_event_tests {
     #[test]
     fn test_update_event_theme() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::Some(String::from_str(&env, "Updated Theme")),
             &Option::None,
@@ -619,10 +4255,11 @@ mod update_event_tests {
     #[test]
     fn test_update_event_ticket_price() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
         let new_price = 2000_0000000i128;
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::None,
             &Option::Some(new_price),
@@ -638,9 +4275,10 @@ mod update_event_tests {
     #[test]
     fn test_update_event_total_tickets() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::None,
             &Option::None,
@@ -656,11 +4294,12 @@ mod update_event_tests {
     #[test]
     fn test_update_event_dates() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
         let new_start = env.ledger().timestamp() + 172800;
         let new_end = new_start + 86400;
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::None,
             &Option::None,
@@ -677,9 +4316,10 @@ mod update_event_tests {
     #[test]
     fn test_update_event_emits_event() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::Some(String::from_str(&env, "Emit Test")),
             &Option::None,
@@ -697,10 +4337,11 @@ mod update_event_tests {
     #[should_panic(expected = "Cannot update a canceled event")]
     fn test_update_event_canceled_fails() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
-        client.cancel_event(&event_id);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
+        client.cancel_event(&organizer, &event_id);
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::Some(String::from_str(&env, "Should fail")),
             &Option::None,
@@ -720,7 +4361,7 @@ mod update_event_tests {
         let mock_addr = env.register(MockContract, ());
         let organizer = Address::generate(&env);
         let buyer = Address::generate(&env);
-        client.initialize(&mock_addr);
+        client.initialize(&mock_addr, &Address::generate(&env));
 
         let start_date = env.ledger().timestamp() + 86400;
         let end_date = start_date + 86400;
@@ -733,11 +4374,13 @@ mod update_event_tests {
             &100i128,
             &10u128,
             &mock_addr,
+            &TransferPolicy::Free,
         );
-        client.purchase_ticket(&buyer, &event_id);
-        client.purchase_ticket(&Address::generate(&env), &event_id);
+        client.purchase_ticket(&buyer, &event_id, &0, &None);
+        client.purchase_ticket(&Address::generate(&env), &event_id, &0, &None);
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::None,
             &Option::None,
@@ -751,12 +4394,13 @@ mod update_event_tests {
     #[should_panic(expected = "Start date cannot be in the past")]
     fn test_update_event_start_date_past_fails() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
         env.ledger()
             .set_timestamp(env.ledger().timestamp() + 86400 * 2);
         let past_start = env.ledger().timestamp() - 3600;
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::None,
             &Option::None,
@@ -770,11 +4414,12 @@ mod update_event_tests {
     #[should_panic(expected = "Start date must be before end date")]
     fn test_update_event_end_before_start_fails() {
         let env = Env::default();
-        let (client, _organizer, event_id) = setup_event_for_update(&env);
+        let (client, organizer, event_id) = setup_event_for_update(&env);
         let start_date = env.ledger().timestamp() + 86400;
         let end_before_start = start_date - 3600;
 
         client.update_event(
+            &organizer,
             &event_id,
             &Option::None,
             &Option::None,
@@ -792,9 +4437,10 @@ mod update_event_tests {
         let client = EventManagerClient::new(&env, &contract_id);
         let mock_addr = env.register(MockContract, ());
         env.mock_all_auths();
-        client.initialize(&mock_addr);
+        client.initialize(&mock_addr, &Address::generate(&env));
 
         client.update_event(
+            &Address::generate(&env),
             &999u32,
             &Option::None,
             &Option::None,
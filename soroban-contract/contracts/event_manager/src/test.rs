@@ -1,7 +1,27 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address, BytesN, Env};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig, Events, Ledger},
+    vec, Address, BytesN, Env,
+};
+
+#[contracttype]
+enum MockKey {
+    NextTokenId,
+    Owner(u128),
+    Tier(u128),
+    HasTicket(Address),
+    MaxTicketsPerWallet,
+    MaxSupply,
+    Name,
+    CheckedIn(u128),
+    Approved(u128),
+    TransferApproval(u128),
+    TransferLock,
+}
 
 #[contract]
 pub struct MockContract;
@@ -9,19 +29,218 @@ pub struct MockContract;
 #[contractimpl]
 impl MockContract {
     // Factory method
-    pub fn deploy_ticket(env: Env, _minter: Address, _salt: BytesN<32>) -> Address {
+    pub fn deploy_ticket(
+        env: Env,
+        _minter: Address,
+        _salt: BytesN<32>,
+        _max_tickets_per_wallet: u32,
+        max_supply: u128,
+        _transfer_policy: TransferPolicy,
+    ) -> Address {
+        env.storage()
+            .instance()
+            .set(&MockKey::MaxSupply, &max_supply);
         env.current_contract_address()
     }
 
-    // NFT method
-    pub fn mint_ticket_nft(_env: Env, _recipient: Address) -> u128 {
-        1
+    // NFT methods
+    pub fn mint_ticket_nft(env: Env, recipient: Address, tier_id: u32) -> u128 {
+        if env
+            .storage()
+            .instance()
+            .has(&MockKey::HasTicket(recipient.clone()))
+        {
+            panic!("User already has ticket");
+        }
+        env.storage()
+            .instance()
+            .set(&MockKey::HasTicket(recipient.clone()), &true);
+        let token_id: u128 = env
+            .storage()
+            .instance()
+            .get(&MockKey::NextTokenId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&MockKey::Owner(token_id), &recipient);
+        env.storage()
+            .instance()
+            .set(&MockKey::Tier(token_id), &tier_id);
+        env.storage()
+            .instance()
+            .set(&MockKey::NextTokenId, &(token_id + 1));
+        token_id
+    }
+
+    pub fn owner_of(env: Env, token_id: u128) -> Address {
+        env.storage()
+            .instance()
+            .get(&MockKey::Owner(token_id))
+            .expect("Invalid token id")
+    }
+
+    pub fn tier_of(env: Env, token_id: u128) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MockKey::Tier(token_id))
+            .expect("Invalid token id")
+    }
+
+    pub fn balance_of(env: Env, owner: Address) -> u128 {
+        env.storage()
+            .instance()
+            .has(&MockKey::HasTicket(owner))
+            .into()
+    }
+
+    pub fn set_max_tickets_per_wallet(env: Env, limit: u32) {
+        env.storage()
+            .instance()
+            .set(&MockKey::MaxTicketsPerWallet, &limit);
+    }
+
+    pub fn max_tickets_per_wallet(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MockKey::MaxTicketsPerWallet)
+            .unwrap_or(1)
+    }
+
+    pub fn set_max_supply(env: Env, max_supply: u128) {
+        env.storage()
+            .instance()
+            .set(&MockKey::MaxSupply, &max_supply);
+    }
+
+    pub fn max_supply(env: Env) -> u128 {
+        env.storage()
+            .instance()
+            .get(&MockKey::MaxSupply)
+            .unwrap_or(0)
+    }
+
+    pub fn set_metadata(env: Env, name: String, _symbol: String, _base_uri: String) {
+        env.storage().instance().set(&MockKey::Name, &name);
+    }
+
+    pub fn name(env: Env) -> String {
+        env.storage().instance().get(&MockKey::Name).unwrap()
+    }
+
+    pub fn burn(env: Env, token_id: u128) {
+        let owner = Self::owner_of(env.clone(), token_id);
+        env.storage().instance().remove(&MockKey::HasTicket(owner));
+        env.storage().instance().remove(&MockKey::Owner(token_id));
+    }
+
+    pub fn check_in(env: Env, token_id: u128) -> u64 {
+        let timestamp = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&MockKey::CheckedIn(token_id), &timestamp);
+        timestamp
+    }
+
+    pub fn is_checked_in(env: Env, token_id: u128) -> bool {
+        env.storage().instance().has(&MockKey::CheckedIn(token_id))
+    }
+
+    pub fn approve_transfer(env: Env, token_id: u128, to: Address) {
+        env.storage()
+            .instance()
+            .set(&MockKey::TransferApproval(token_id), &to);
+    }
+
+    pub fn get_transfer_approval(env: Env, token_id: u128) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&MockKey::TransferApproval(token_id))
+    }
+
+    pub fn set_transfer_lock(env: Env, locked_from: u64, locked_until: u64) {
+        env.storage()
+            .instance()
+            .set(&MockKey::TransferLock, &(locked_from, locked_until));
+    }
+
+    pub fn clear_transfer_lock(env: Env) {
+        env.storage().instance().remove(&MockKey::TransferLock);
+    }
+
+    pub fn transfer_lock(env: Env) -> Option<(u64, u64)> {
+        env.storage().instance().get(&MockKey::TransferLock)
+    }
+
+    pub fn is_valid(env: Env, token_id: u128) -> bool {
+        env.storage().instance().has(&MockKey::Owner(token_id))
+    }
+
+    pub fn approve(env: Env, _approver: Address, approved: Option<Address>, token_id: u128) {
+        env.storage()
+            .instance()
+            .set(&MockKey::Approved(token_id), &approved);
+    }
+
+    pub fn get_approved(env: Env, token_id: u128) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&MockKey::Approved(token_id))
+            .unwrap_or(None)
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, token_id: u128) {
+        env.storage().instance().remove(&MockKey::HasTicket(from));
+        env.storage()
+            .instance()
+            .set(&MockKey::HasTicket(to.clone()), &true);
+        env.storage().instance().set(&MockKey::Owner(token_id), &to);
+        env.storage()
+            .instance()
+            .remove(&MockKey::Approved(token_id));
     }
 
     // Token method
     pub fn transfer(_env: Env, _from: Address, _to: Address, _amount: i128) {}
 }
 
+/// The real TicketNft contract, for tests where the mock is too lenient
+mod real_nft {
+    use super::*;
+
+    pub mod ticket_nft_contract {
+        soroban_sdk::contractimport!(
+            file = "../../target/wasm32-unknown-unknown/release/ticket_nft.wasm"
+        );
+    }
+
+    #[contract]
+    pub struct NftFactory;
+
+    #[contractimpl]
+    impl NftFactory {
+        pub fn __constructor(env: Env, wasm_hash: BytesN<32>) {
+            env.storage().instance().set(&MockKey::Name, &wasm_hash);
+        }
+
+        pub fn deploy_ticket(
+            env: Env,
+            minter: Address,
+            salt: BytesN<32>,
+            max_tickets_per_wallet: u32,
+            max_supply: u128,
+            transfer_policy: TransferPolicy,
+        ) -> Address {
+            let wasm_hash: BytesN<32> = env.storage().instance().get(&MockKey::Name).unwrap();
+            env.deployer()
+                .with_address(env.current_contract_address(), salt)
+                .deploy_v2(
+                    wasm_hash,
+                    (minter, max_tickets_per_wallet, max_supply, transfer_policy),
+                )
+        }
+    }
+}
+
 #[test]
 fn test_create_event() {
     let env = Env::default();
@@ -35,7 +254,7 @@ fn test_create_event() {
     env.mock_all_auths();
 
     // Initialize
-    client.initialize(&mock_addr);
+    client.initialize(&mock_addr, &Address::generate(&env));
 
     // Create event
     let theme = String::from_str(&env, "Rust Conference 2026");
@@ -55,6 +274,7 @@ fn test_create_event() {
         &ticket_price,
         &total_tickets,
         &payment_token,
+        &TransferPolicy::Free,
     );
 
     assert_eq!(event_id, 0);
@@ -65,7 +285,7 @@ fn test_create_event() {
     assert_eq!(event.organizer, organizer);
     assert_eq!(event.total_tickets, total_tickets);
     assert_eq!(event.tickets_sold, 0);
-    assert_eq!(event.is_canceled, false);
+    assert!(!event.is_canceled);
 }
 
 #[test]
@@ -80,7 +300,7 @@ fn test_create_event_past_date() {
 
     env.mock_all_auths();
     env.ledger().set_timestamp(1000);
-    client.initialize(&mock_addr);
+    client.initialize(&mock_addr, &Address::generate(&env));
 
     let theme = String::from_str(&env, "Past Event");
     let event_type = String::from_str(&env, "Conference");
@@ -96,6 +316,7 @@ fn test_create_event_past_date() {
         &1000_0000000,
         &100,
         &Address::generate(&env),
+        &TransferPolicy::Free,
     );
 }
 
@@ -109,7 +330,7 @@ fn test_cancel_event() {
     let organizer = Address::generate(&env);
 
     env.mock_all_auths();
-    client.initialize(&mock_addr);
+    client.initialize(&mock_addr, &Address::generate(&env));
 
     let event_id = client.create_event(
         &organizer,
@@ -120,12 +341,13 @@ fn test_cancel_event() {
         &1000_0000000,
         &100,
         &Address::generate(&env),
+        &TransferPolicy::Free,
     );
 
-    client.cancel_event(&event_id);
+    client.cancel_event(&organizer, &event_id);
 
     let event = client.get_event(&event_id);
-    assert_eq!(event.is_canceled, true);
+    assert!(event.is_canceled);
 }
 
 #[test]
@@ -143,7 +365,7 @@ fn test_purchase_ticket() {
     let buyer = Address::generate(&env);
 
     // Initialize
-    client.initialize(&mock_addr);
+    client.initialize(&mock_addr, &Address::generate(&env));
 
     // Create event
     let theme = String::from_str(&env, "Test");
@@ -162,12 +384,2736 @@ fn test_purchase_ticket() {
         &ticket_price,
         &total_tickets,
         &mock_addr, // use mock for payment token too
+        &TransferPolicy::Free,
     );
 
     // Purchase ticket
-    client.purchase_ticket(&buyer, &event_id);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
 
     // Verify tickets sold incremented
     let event = client.get_event(&event_id);
     assert_eq!(event.tickets_sold, 1);
 }
+
+fn setup_escrow_event(env: &Env) -> (EventManagerClient<'_>, Address, Address, u32) {
+    let contract_id = env.register(EventManager, ());
+    let client = EventManagerClient::new(env, &contract_id);
+
+    let mock_addr = env.register(MockContract, ());
+    let organizer = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let payment_token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    env.mock_all_auths();
+    client.initialize(&mock_addr, &Address::generate(env));
+
+    let start_date = env.ledger().timestamp() + 86400;
+    let end_date = start_date + 86400;
+    let event_id = client.create_event(
+        &organizer,
+        &String::from_str(env, "Escrow Event"),
+        &String::from_str(env, "Concert"),
+        &start_date,
+        &end_date,
+        &100i128,
+        &10u128,
+        &payment_token,
+        &TransferPolicy::Free,
+    );
+
+    (client, organizer, payment_token, event_id)
+}
+
+fn fund_buyer(env: &Env, payment_token: &Address) -> Address {
+    let buyer = Address::generate(env);
+    token::StellarAssetClient::new(env, payment_token).mint(&buyer, &1000);
+    buyer
+}
+
+#[test]
+fn test_purchase_ticket_escrows_payment() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&buyer), 900);
+    assert_eq!(token_client.balance(&organizer), 0);
+    assert_eq!(token_client.balance(&client.address), 100);
+    assert_eq!(client.get_event_balance(&event_id), 100);
+}
+
+#[test]
+fn test_withdraw_funds_after_event_end() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    let event = client.get_event(&event_id);
+    env.ledger().set_timestamp(event.end_date);
+
+    let withdrawn = client.withdraw_funds(&organizer, &event_id);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(withdrawn, 200);
+    assert_eq!(token_client.balance(&organizer), 200);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.get_event_balance(&event_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Event has not ended yet")]
+fn test_withdraw_funds_before_end_fails() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    client.withdraw_funds(&organizer, &event_id);
+}
+
+#[test]
+#[should_panic(expected = "Cannot withdraw funds for a canceled event")]
+fn test_withdraw_funds_canceled_event_fails() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    client.cancel_event(&organizer, &event_id);
+
+    let event = client.get_event(&event_id);
+    env.ledger().set_timestamp(event.end_date);
+
+    client.withdraw_funds(&organizer, &event_id);
+}
+
+#[test]
+#[should_panic(expected = "No funds to withdraw")]
+fn test_withdraw_funds_twice_fails() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    let event = client.get_event(&event_id);
+    env.ledger().set_timestamp(event.end_date);
+
+    client.withdraw_funds(&organizer, &event_id);
+    client.withdraw_funds(&organizer, &event_id);
+}
+
+#[test]
+fn test_claim_refund_after_cancel() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_fee_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+    client.cancel_event(&organizer, &event_id);
+
+    let refunded = client.claim_refund(&event_id, &1u128);
+
+    // The platform fee is refunded along with the ticket price
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(refunded, 100);
+    assert_eq!(token_client.balance(&buyer), 1000);
+    assert_eq!(client.get_event_balance(&event_id), 0);
+    assert_eq!(client.get_event_fees(&event_id), 0);
+    assert!(client.is_refund_claimed(&event_id, &1u128));
+}
+
+#[test]
+fn test_claim_refund_pays_original_price() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_fee_escrow_event(&env);
+    let early_buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&early_buyer, &event_id, &0, &None);
+
+    client.update_event(
+        &organizer,
+        &event_id,
+        &Option::None,
+        &Option::Some(250i128),
+        &Option::None,
+        &Option::None,
+        &Option::None,
+    );
+    let late_buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&late_buyer, &event_id, &0, &None);
+    client.cancel_event(&organizer, &event_id);
+
+    assert_eq!(client.claim_refund(&event_id, &1u128), 100);
+    assert_eq!(client.claim_refund(&event_id, &2u128), 250);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&early_buyer), 1000);
+    assert_eq!(token_client.balance(&late_buyer), 1000);
+    assert_eq!(client.get_event_balance(&event_id), 0);
+    assert_eq!(client.get_event_fees(&event_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Refund already claimed")]
+fn test_claim_refund_twice_fails() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_fee_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    client.cancel_event(&organizer, &event_id);
+
+    assert_eq!(client.claim_refund(&event_id, &1u128), 100);
+    client.claim_refund(&event_id, &1u128);
+}
+
+#[test]
+#[should_panic(expected = "Event is not canceled")]
+fn test_claim_refund_active_event_fails() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_fee_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    client.claim_refund(&event_id, &1u128);
+}
+
+#[test]
+#[should_panic]
+fn test_claim_refund_requires_ticket_owner() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_fee_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    client.cancel_event(&organizer, &event_id);
+
+    // Drop the blanket auth mock: the owner has not signed this claim
+    env.set_auths(&[]);
+    client.claim_refund(&event_id, &1u128);
+}
+
+#[test]
+#[should_panic(expected = "Cannot cancel an event that has ended")]
+fn test_cancel_after_payout_cannot_drain_other_events() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+
+    // Another organizer's event sharing the same payment token
+    let other_event = client.create_event(
+        &Address::generate(&env),
+        &String::from_str(&env, "Other Event"),
+        &String::from_str(&env, "Concert"),
+        &(env.ledger().timestamp() + 86400),
+        &(env.ledger().timestamp() + 10 * 86400),
+        &100i128,
+        &10u128,
+        &payment_token,
+        &TransferPolicy::Free,
+    );
+    let other_buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&other_buyer, &other_event, &0, &None);
+
+    // The organizer buys their own ticket and withdraws once the event ends
+    token::StellarAssetClient::new(&env, &payment_token).mint(&organizer, &100);
+    client.purchase_ticket(&organizer, &event_id, &0, &None);
+    env.ledger()
+        .set_timestamp(client.get_event(&event_id).end_date);
+    client.withdraw_funds(&organizer, &event_id);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&client.address), 100);
+    assert_eq!(client.get_event_balance(&other_event), 100);
+
+    client.cancel_event(&organizer, &event_id);
+}
+
+#[test]
+#[should_panic(expected = "Cannot cancel after revenue has been withdrawn")]
+fn test_cancel_after_split_payout_fails() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let payee = Address::generate(&env);
+    client.set_revenue_splits(
+        &event_id,
+        &vec![
+            &env,
+            RevenueSplit {
+                payee: organizer.clone(),
+                bps: 5_000,
+            },
+            RevenueSplit {
+                payee: payee.clone(),
+                bps: 5_000,
+            },
+        ],
+    );
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    // Push the event's end out after the payout so only the payout blocks cancelation
+    let event = client.get_event(&event_id);
+    env.ledger().set_timestamp(event.end_date);
+    client.claim_revenue_share(&payee, &event_id);
+    env.ledger().set_timestamp(event.start_date - 1);
+
+    client.cancel_event(&organizer, &event_id);
+}
+
+#[test]
+fn test_create_event_has_default_tier() {
+    let env = Env::default();
+    let (client, _organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    let tiers = client.get_ticket_tiers(&event_id);
+    assert_eq!(tiers.len(), 1);
+
+    let default_tier = tiers.get(0).unwrap();
+    assert_eq!(default_tier.id, 0);
+    assert_eq!(default_tier.price, 100);
+    assert_eq!(default_tier.total_supply, 10);
+    assert_eq!(default_tier.sold, 0);
+}
+
+#[test]
+fn test_purchase_ticket_in_tier() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let vip_tier = client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "VIP"),
+        &300i128,
+        &2u128,
+        &None,
+        &None,
+    );
+    assert_eq!(vip_tier, 1);
+
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &vip_tier, &None);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&buyer), 700);
+    assert_eq!(client.get_ticket_payment(&event_id, &1u128), Some(300));
+    assert_eq!(client.get_ticket_tier(&event_id, &vip_tier).sold, 1);
+    assert_eq!(client.get_ticket_tier(&event_id, &0).sold, 0);
+    assert_eq!(client.get_event(&event_id).tickets_sold, 1);
+
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    assert_eq!(nft.tier_of(&1u128), vip_tier);
+}
+
+#[test]
+#[should_panic(expected = "Tier is sold out")]
+fn test_purchase_ticket_tier_sold_out() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let vip_tier = client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "VIP"),
+        &300i128,
+        &1u128,
+        &None,
+        &None,
+    );
+
+    client.purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &vip_tier,
+        &None,
+    );
+    client.purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &vip_tier,
+        &None,
+    );
+}
+
+#[test]
+fn test_purchase_ticket_tier_sale_window() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let now = env.ledger().timestamp();
+    let early_bird = client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "Early Bird"),
+        &50i128,
+        &5u128,
+        &Some(now + 100),
+        &Some(now + 200),
+    );
+
+    let result = client.try_purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &early_bird,
+        &None,
+    );
+    assert!(result.is_err());
+
+    env.ledger().set_timestamp(now + 100);
+    client.purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &early_bird,
+        &None,
+    );
+
+    env.ledger().set_timestamp(now + 200);
+    let result = client.try_purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &early_bird,
+        &None,
+    );
+    assert!(result.is_err());
+
+    assert_eq!(client.get_ticket_tier(&event_id, &early_bird).sold, 1);
+}
+
+#[test]
+fn test_update_ticket_tier() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let vip_tier = client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "VIP"),
+        &300i128,
+        &2u128,
+        &None,
+        &None,
+    );
+
+    client.update_ticket_tier(
+        &organizer,
+        &event_id,
+        &vip_tier,
+        &Some(String::from_str(&env, "VIP Lounge")),
+        &Some(400i128),
+        &Some(4u128),
+        &None,
+        &None,
+    );
+
+    let tier = client.get_ticket_tier(&event_id, &vip_tier);
+    assert_eq!(tier.name, String::from_str(&env, "VIP Lounge"));
+    assert_eq!(tier.price, 400);
+    assert_eq!(tier.total_supply, 4);
+}
+
+#[test]
+fn test_update_event_syncs_default_tier() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &Some(150i128),
+        &Some(20u128),
+        &None,
+        &None,
+    );
+
+    let default_tier = client.get_ticket_tier(&event_id, &0);
+    assert_eq!(default_tier.price, 150);
+    assert_eq!(default_tier.total_supply, 20);
+}
+
+#[test]
+#[should_panic(expected = "Tier supply cannot exceed total tickets")]
+fn test_create_ticket_tier_exceeding_event_supply_fails() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "VIP"),
+        &300i128,
+        &11u128,
+        &None,
+        &None,
+    );
+}
+
+fn create_listing_event(
+    env: &Env,
+    client: &EventManagerClient,
+    organizer: &Address,
+    event_type: &str,
+    start_date: u64,
+) -> u32 {
+    client.create_event(
+        organizer,
+        &String::from_str(env, "Listing Event"),
+        &String::from_str(env, event_type),
+        &start_date,
+        &(start_date + 1000),
+        &100i128,
+        &10u128,
+        &Address::generate(env),
+        &TransferPolicy::Free,
+    )
+}
+
+#[test]
+fn test_get_events_paginated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&env.register(MockContract, ()), &Address::generate(&env));
+    let organizer = Address::generate(&env);
+
+    for _ in 0..5 {
+        create_listing_event(&env, &client, &organizer, "Concert", 1000);
+    }
+
+    let page = client.get_events(&1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, 1);
+    assert_eq!(page.get(1).unwrap().id, 2);
+
+    let last_page = client.get_events(&4, &10);
+    assert_eq!(last_page.len(), 1);
+    assert_eq!(last_page.get(0).unwrap().id, 4);
+
+    assert_eq!(client.get_events(&5, &10).len(), 0);
+}
+
+#[test]
+fn test_get_events_by_organizer_and_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&env.register(MockContract, ()), &Address::generate(&env));
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    create_listing_event(&env, &client, &alice, "Concert", 1000);
+    create_listing_event(&env, &client, &bob, "Conference", 1000);
+    create_listing_event(&env, &client, &alice, "Conference", 1000);
+    create_listing_event(&env, &client, &alice, "Concert", 1000);
+
+    assert_eq!(client.get_organizer_event_count(&alice), 3);
+    let alice_events = client.get_events_by_organizer(&alice, &1, &10);
+    assert_eq!(alice_events.len(), 2);
+    assert_eq!(alice_events.get(0).unwrap().id, 2);
+    assert_eq!(alice_events.get(1).unwrap().id, 3);
+
+    let conference = String::from_str(&env, "Conference");
+    assert_eq!(client.get_type_event_count(&conference), 2);
+    let conferences = client.get_events_by_type(&conference, &0, &10);
+    assert_eq!(conferences.get(0).unwrap().id, 1);
+    assert_eq!(conferences.get(1).unwrap().id, 2);
+}
+
+#[test]
+fn test_get_events_by_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&env.register(MockContract, ()), &Address::generate(&env));
+    let organizer = Address::generate(&env);
+
+    let ended = create_listing_event(&env, &client, &organizer, "Concert", 1000);
+    let active = create_listing_event(&env, &client, &organizer, "Concert", 2000);
+    let upcoming = create_listing_event(&env, &client, &organizer, "Concert", 5000);
+    let canceled = create_listing_event(&env, &client, &organizer, "Concert", 5000);
+    client.cancel_event(&organizer, &canceled);
+
+    env.ledger().set_timestamp(2500);
+
+    assert_eq!(client.get_event_status(&ended), EventStatus::Ended);
+    assert_eq!(client.get_event_status(&active), EventStatus::Active);
+    assert_eq!(client.get_event_status(&upcoming), EventStatus::Upcoming);
+    assert_eq!(client.get_event_status(&canceled), EventStatus::Canceled);
+
+    let upcoming_events = client.get_events_by_status(&EventStatus::Upcoming, &0, &10);
+    assert_eq!(upcoming_events.len(), 1);
+    assert_eq!(upcoming_events.get(0).unwrap().id, upcoming);
+
+    let canceled_events = client.get_events_by_status(&EventStatus::Canceled, &0, &10);
+    assert_eq!(canceled_events.len(), 1);
+    assert_eq!(canceled_events.get(0).unwrap().id, canceled);
+
+    let active_events = client.get_events_by_status(&EventStatus::Active, &0, &10);
+    assert_eq!(active_events.len(), 1);
+    assert_eq!(active_events.get(0).unwrap().id, active);
+
+    let ended_events = client.get_events_by_status(&EventStatus::Ended, &0, &10);
+    assert_eq!(ended_events.len(), 1);
+    assert_eq!(ended_events.get(0).unwrap().id, ended);
+}
+
+#[test]
+fn test_get_events_by_status_pages_are_full() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&env.register(MockContract, ()), &Address::generate(&env));
+    let organizer = Address::generate(&env);
+
+    // Interleave ended and upcoming events so ID ranges mix statuses
+    let mut upcoming = std::vec::Vec::new();
+    let mut ended = std::vec::Vec::new();
+    for i in 0..3u64 {
+        ended.push(create_listing_event(
+            &env,
+            &client,
+            &organizer,
+            "Concert",
+            1000 + i,
+        ));
+        upcoming.push(create_listing_event(
+            &env,
+            &client,
+            &organizer,
+            "Concert",
+            9000 - i,
+        ));
+    }
+
+    env.ledger().set_timestamp(5000);
+
+    // Upcoming events come soonest first, ended events earliest first
+    let page = client.get_events_by_status(&EventStatus::Upcoming, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, upcoming[2]);
+    assert_eq!(page.get(1).unwrap().id, upcoming[1]);
+    let page = client.get_events_by_status(&EventStatus::Upcoming, &2, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, upcoming[0]);
+
+    let page = client.get_events_by_status(&EventStatus::Ended, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, ended[0]);
+    assert_eq!(page.get(1).unwrap().id, ended[1]);
+
+    // Rescheduling moves the event within the index
+    client.reschedule_event(&organizer, &upcoming[0], &6000, &7000);
+    let page = client.get_events_by_status(&EventStatus::Upcoming, &0, &1);
+    assert_eq!(page.get(0).unwrap().id, upcoming[0]);
+}
+
+#[test]
+fn test_get_active_events_pages_are_full() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&env.register(MockContract, ()), &Address::generate(&env));
+    let organizer = Address::generate(&env);
+
+    let festival = |start: u64, end: u64| {
+        client.create_event(
+            &organizer,
+            &String::from_str(&env, "Festival"),
+            &String::from_str(&env, "Festival"),
+            &start,
+            &end,
+            &100i128,
+            &10u128,
+            &Address::generate(&env),
+            &TransferPolicy::Free,
+        )
+    };
+    let first = festival(2000, 10_000);
+    create_listing_event(&env, &client, &organizer, "Concert", 100);
+    create_listing_event(&env, &client, &organizer, "Concert", 3000);
+    let second = festival(2200, 9000);
+
+    env.ledger().set_timestamp(2500);
+
+    // Ended and upcoming events in between don't leave gaps in the pages
+    let page = client.get_events_by_status(&EventStatus::Active, &0, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, first);
+    let page = client.get_events_by_status(&EventStatus::Active, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, second);
+    let page = client.get_events_by_status(&EventStatus::Active, &2, &1);
+    assert_eq!(page.len(), 0);
+}
+
+#[test]
+fn test_ended_events_are_archived_in_end_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&env.register(MockContract, ()), &Address::generate(&env));
+    let organizer = Address::generate(&env);
+
+    // Spread events over several day buckets, out of creation order
+    const DAY: u64 = 24 * 60 * 60;
+    let ended: std::vec::Vec<u32> = [3 * DAY, DAY, 2 * DAY]
+        .iter()
+        .map(|start| create_listing_event(&env, &client, &organizer, "Concert", *start))
+        .collect();
+    let upcoming = create_listing_event(&env, &client, &organizer, "Concert", 10 * DAY);
+
+    env.ledger().set_timestamp(5 * DAY);
+
+    // Archived events come first, followed by ended events still in the live index
+    assert_eq!(client.archive_ended_events(&2), 2);
+    let page = client.get_events_by_status(&EventStatus::Ended, &0, &10);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get(0).unwrap().id, ended[1]);
+    assert_eq!(page.get(1).unwrap().id, ended[2]);
+    assert_eq!(page.get(2).unwrap().id, ended[0]);
+    let page = client.get_events_by_status(&EventStatus::Ended, &1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, ended[2]);
+    assert_eq!(page.get(1).unwrap().id, ended[0]);
+
+    // Creating an event archives the rest
+    create_listing_event(&env, &client, &organizer, "Concert", 6 * DAY);
+    assert_eq!(client.archive_ended_events(&10), 0);
+    let page = client.get_events_by_status(&EventStatus::Ended, &2, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, ended[0]);
+
+    let page = client.get_events_by_status(&EventStatus::Upcoming, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, upcoming);
+}
+
+#[test]
+#[should_panic(expected = "Cannot reschedule an event that has ended")]
+fn test_update_ended_event_dates_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&env.register(MockContract, ()), &Address::generate(&env));
+    let organizer = Address::generate(&env);
+    let event_id = create_listing_event(&env, &client, &organizer, "Concert", 1000);
+
+    env.ledger().set_timestamp(5000);
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &None,
+        &None,
+        &Some(6000),
+        &Some(7000),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot reschedule an event that has ended")]
+fn test_reschedule_after_payout_cannot_drain_other_events() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+
+    // Another organizer's event sharing the same payment token
+    let other_event = client.create_event(
+        &Address::generate(&env),
+        &String::from_str(&env, "Other Event"),
+        &String::from_str(&env, "Concert"),
+        &(env.ledger().timestamp() + 86400),
+        &(env.ledger().timestamp() + 10 * 86400),
+        &100i128,
+        &10u128,
+        &payment_token,
+        &TransferPolicy::Free,
+    );
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &other_event, &0, &None);
+
+    token::StellarAssetClient::new(&env, &payment_token).mint(&organizer, &100);
+    client.purchase_ticket(&organizer, &event_id, &0, &None);
+    let end_date = client.get_event(&event_id).end_date;
+    env.ledger().set_timestamp(end_date);
+    client.withdraw_funds(&organizer, &event_id);
+    assert_eq!(client.get_event_balance(&other_event), 100);
+
+    client.reschedule_event(
+        &organizer,
+        &event_id,
+        &(end_date + 86400),
+        &(end_date + 2 * 86400),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot reschedule after revenue has been withdrawn")]
+fn test_reschedule_after_split_payout_fails() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let payee = Address::generate(&env);
+    client.set_revenue_splits(
+        &event_id,
+        &vec![
+            &env,
+            RevenueSplit {
+                payee: organizer.clone(),
+                bps: 5_000,
+            },
+            RevenueSplit {
+                payee: payee.clone(),
+                bps: 5_000,
+            },
+        ],
+    );
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    // Rewind after the payout so only the payout blocks the reschedule
+    let event = client.get_event(&event_id);
+    env.ledger().set_timestamp(event.end_date);
+    client.claim_revenue_share(&payee, &event_id);
+    env.ledger().set_timestamp(event.start_date - 1);
+
+    client.reschedule_event(
+        &organizer,
+        &event_id,
+        &(event.end_date + 86400),
+        &(event.end_date + 2 * 86400),
+    );
+}
+
+#[test]
+fn test_reschedule_event_opens_refund_window() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+
+    let new_start = env.ledger().timestamp() + 30 * 86400;
+    let new_end = new_start + 86400;
+    client.reschedule_event(&organizer, &event_id, &new_start, &new_end);
+
+    let deadline = env.ledger().timestamp() + 7 * 86400;
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        soroban_sdk::vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "event_rescheduled"),).into_val(&env),
+                (event_id, new_start, new_end, deadline).into_val(&env),
+            )
+        ]
+    );
+
+    let event = client.get_event(&event_id);
+    assert_eq!(event.start_date, new_start);
+    assert_eq!(event.end_date, new_end);
+    assert_eq!(
+        client
+            .get_reschedule_window(&event_id)
+            .unwrap()
+            .refund_deadline,
+        deadline
+    );
+
+    assert_eq!(client.claim_refund(&event_id, &1u128), 100);
+    assert_eq!(
+        token::Client::new(&env, &payment_token).balance(&buyer),
+        1000
+    );
+    assert_eq!(client.get_event(&event_id).tickets_sold, 0);
+}
+
+#[test]
+#[should_panic(expected = "Event is not canceled")]
+fn test_reschedule_refund_after_window_fails() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    client.set_reschedule_refund_window(&organizer, &event_id, &3600);
+    let new_start = env.ledger().timestamp() + 30 * 86400;
+    client.reschedule_event(&organizer, &event_id, &new_start, &(new_start + 86400));
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 3600);
+    client.claim_refund(&event_id, &1u128);
+}
+
+#[test]
+#[should_panic(expected = "Event is not canceled")]
+fn test_reschedule_refund_not_available_for_new_tickets() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+
+    env.ledger().set_timestamp(100);
+    let new_start = env.ledger().timestamp() + 30 * 86400;
+    client.reschedule_event(&organizer, &event_id, &new_start, &(new_start + 86400));
+
+    env.ledger().set_timestamp(200);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    client.claim_refund(&event_id, &1u128);
+}
+
+#[test]
+#[should_panic(expected = "Cannot reschedule a canceled event")]
+fn test_reschedule_canceled_event_fails() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    client.cancel_event(&organizer, &event_id);
+
+    let new_start = env.ledger().timestamp() + 30 * 86400;
+    client.reschedule_event(&organizer, &event_id, &new_start, &(new_start + 86400));
+}
+
+#[test]
+fn test_purchase_tickets_for_group() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    let friend1 = Address::generate(&env);
+    let friend2 = Address::generate(&env);
+
+    let token_ids = client.purchase_tickets(
+        &buyer,
+        &event_id,
+        &0,
+        &soroban_sdk::vec![&env, buyer.clone(), friend1.clone(), friend2.clone()],
+        &None,
+    );
+
+    assert_eq!(token_ids, soroban_sdk::vec![&env, 1u128, 2u128, 3u128]);
+    assert_eq!(
+        token::Client::new(&env, &payment_token).balance(&buyer),
+        700
+    );
+    assert_eq!(client.get_event_balance(&event_id), 300);
+    assert_eq!(client.get_event(&event_id).tickets_sold, 3);
+    assert_eq!(client.get_ticket_tier(&event_id, &0).sold, 3);
+
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    assert_eq!(nft.owner_of(&2u128), friend1);
+    assert_eq!(nft.owner_of(&3u128), friend2);
+    assert_eq!(client.get_ticket_payment(&event_id, &3u128), Some(100));
+}
+
+#[test]
+fn test_purchase_tickets_fails_as_whole_when_recipient_has_ticket() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    let holder = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&holder, &event_id, &0, &None);
+
+    let result = client.try_purchase_tickets(
+        &buyer,
+        &event_id,
+        &0,
+        &soroban_sdk::vec![&env, Address::generate(&env), holder],
+        &None,
+    );
+    assert!(result.is_err());
+
+    assert_eq!(
+        token::Client::new(&env, &payment_token).balance(&buyer),
+        1000
+    );
+    assert_eq!(client.get_event(&event_id).tickets_sold, 1);
+    assert_eq!(client.get_event_balance(&event_id), 100);
+}
+
+#[test]
+#[should_panic(expected = "Not enough tickets available")]
+fn test_purchase_tickets_exceeding_supply_fails() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+
+    let mut recipients = Vec::new(&env);
+    for _ in 0..11 {
+        recipients.push_back(Address::generate(&env));
+    }
+
+    client.purchase_tickets(&buyer, &event_id, &0, &recipients, &None);
+}
+
+#[test]
+fn test_set_max_tickets_per_wallet() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    client.set_max_tickets_per_wallet(&organizer, &event_id, &4);
+
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    assert_eq!(nft.max_tickets_per_wallet(), 4);
+}
+
+#[test]
+fn test_ticket_supply_cap_follows_total_tickets() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    assert_eq!(nft.max_supply(), 10);
+
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &None,
+        &Some(25u128),
+        &None,
+        &None,
+    );
+    assert_eq!(nft.max_supply(), 25);
+
+    client.update_ticket_tier(
+        &organizer,
+        &event_id,
+        &0,
+        &None,
+        &None,
+        &Some(15u128),
+        &None,
+        &None,
+    );
+    assert_eq!(nft.max_supply(), 15);
+}
+
+#[test]
+fn test_ticket_collection_named_after_event() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    assert_eq!(nft.name(), String::from_str(&env, "Escrow Event"));
+
+    client.set_ticket_metadata(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "Escrow Event VIP"),
+        &String::from_str(&env, "VIP"),
+        &String::from_str(&env, "https://crowdpass.live/tickets/"),
+    );
+    assert_eq!(nft.name(), String::from_str(&env, "Escrow Event VIP"));
+}
+
+fn start_event(env: &Env, client: &EventManagerClient, event_id: u32) {
+    let start_date = client.get_event(&event_id).start_date;
+    env.ledger().with_mut(|li| li.timestamp = start_date);
+}
+
+#[test]
+fn test_check_in() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+
+    let scanner = Address::generate(&env);
+    client.grant_role(&event_id, &scanner, &Role::Scanner);
+    assert!(client.has_role(&event_id, &scanner, &Role::Scanner));
+    assert!(!client.is_checked_in(&event_id, &1u128));
+
+    start_event(&env, &client, event_id);
+    let checked_in_at = client.check_in(&scanner, &event_id, &1u128);
+
+    assert_eq!(checked_in_at, client.get_event(&event_id).start_date);
+    assert!(client.is_checked_in(&event_id, &1u128));
+}
+
+#[test]
+#[should_panic(expected = "Ticket already checked in")]
+fn test_cannot_check_in_twice() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+    let scanner = Address::generate(&env);
+    client.grant_role(&event_id, &scanner, &Role::Scanner);
+    start_event(&env, &client, event_id);
+
+    client.check_in(&scanner, &event_id, &1u128);
+    client.check_in(&scanner, &event_id, &1u128);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_removed_scanner_cannot_check_in() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+    let scanner = Address::generate(&env);
+    client.grant_role(&event_id, &scanner, &Role::Scanner);
+    client.revoke_role(&event_id, &scanner, &Role::Scanner);
+    start_event(&env, &client, event_id);
+
+    client.check_in(&scanner, &event_id, &1u128);
+}
+
+#[test]
+#[should_panic(expected = "Check-in is only allowed during the event")]
+fn test_cannot_check_in_before_event() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+    let scanner = Address::generate(&env);
+    client.grant_role(&event_id, &scanner, &Role::Scanner);
+
+    client.check_in(&scanner, &event_id, &1u128);
+}
+
+#[test]
+#[should_panic(expected = "Ticket already checked in")]
+fn test_cannot_refund_checked_in_ticket() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+    let scanner = Address::generate(&env);
+    client.grant_role(&event_id, &scanner, &Role::Scanner);
+    start_event(&env, &client, event_id);
+    client.check_in(&scanner, &event_id, &1u128);
+
+    client.cancel_event(&organizer, &event_id);
+    client.claim_refund(&event_id, &1u128);
+}
+
+fn sign_checkin(
+    env: &Env,
+    client: &EventManagerClient,
+    signing_key: &SigningKey,
+    event_id: u32,
+    token_id: u128,
+    nonce: u64,
+) -> SignedCheckin {
+    let expires_at = env.ledger().timestamp() + 300;
+    let payload = (
+        client.address.clone(),
+        event_id,
+        token_id,
+        expires_at,
+        nonce,
+    )
+        .to_xdr(env);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = signing_key.sign(&message).to_bytes();
+
+    SignedCheckin {
+        token_id,
+        expires_at,
+        nonce,
+        signature: BytesN::from_array(env, &signature),
+    }
+}
+
+fn setup_signed_checkin(env: &Env) -> (EventManagerClient<'_>, Address, SigningKey, u32) {
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(env);
+    let buyer = fund_buyer(env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(env, &signing_key.verifying_key().to_bytes());
+    client.register_checkin_key(&buyer, &public_key);
+
+    let scanner = Address::generate(env);
+    client.grant_role(&event_id, &scanner, &Role::Scanner);
+    start_event(env, &client, event_id);
+
+    (client, scanner, signing_key, event_id)
+}
+
+#[test]
+fn test_redeem_signed_checkins() {
+    let env = Env::default();
+    let (client, scanner, signing_key, event_id) = setup_signed_checkin(&env);
+
+    let checkin = sign_checkin(&env, &client, &signing_key, event_id, 1, 1);
+    client.redeem_signed_checkins(&scanner, &event_id, &soroban_sdk::vec![&env, checkin]);
+
+    assert!(client.is_checked_in(&event_id, &1u128));
+}
+
+#[test]
+#[should_panic]
+fn test_redeem_signed_checkin_rejects_bad_signature() {
+    let env = Env::default();
+    let (client, scanner, _signing_key, event_id) = setup_signed_checkin(&env);
+
+    let other_key = SigningKey::from_bytes(&[8u8; 32]);
+    let checkin = sign_checkin(&env, &client, &other_key, event_id, 1, 1);
+    client.redeem_signed_checkins(&scanner, &event_id, &soroban_sdk::vec![&env, checkin]);
+}
+
+#[test]
+#[should_panic(expected = "Check-in nonce already used")]
+fn test_redeem_signed_checkin_rejects_replayed_nonce() {
+    let env = Env::default();
+    let (client, scanner, signing_key, event_id) = setup_signed_checkin(&env);
+
+    let checkin = sign_checkin(&env, &client, &signing_key, event_id, 1, 1);
+    client.redeem_signed_checkins(
+        &scanner,
+        &event_id,
+        &soroban_sdk::vec![&env, checkin.clone(), checkin],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Check-in payload expired")]
+fn test_redeem_signed_checkin_rejects_expired_payload() {
+    let env = Env::default();
+    let (client, scanner, signing_key, event_id) = setup_signed_checkin(&env);
+
+    let checkin = sign_checkin(&env, &client, &signing_key, event_id, 1, 1);
+    env.ledger().with_mut(|li| li.timestamp += 301);
+    client.redeem_signed_checkins(&scanner, &event_id, &soroban_sdk::vec![&env, checkin]);
+}
+
+#[test]
+fn test_grant_and_revoke_roles() {
+    let env = Env::default();
+    let (client, _organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let staff = Address::generate(&env);
+
+    client.grant_role(&event_id, &staff, &Role::CoOrganizer);
+    client.grant_role(&event_id, &staff, &Role::Finance);
+    assert!(client.has_role(&event_id, &staff, &Role::CoOrganizer));
+    assert!(!client.has_role(&event_id, &staff, &Role::Scanner));
+    assert_eq!(
+        client.get_roles(&event_id, &staff),
+        soroban_sdk::vec![&env, Role::CoOrganizer, Role::Finance]
+    );
+
+    client.revoke_role(&event_id, &staff, &Role::CoOrganizer);
+    assert_eq!(
+        client.get_roles(&event_id, &staff),
+        soroban_sdk::vec![&env, Role::Finance]
+    );
+}
+
+#[test]
+fn test_co_organizer_can_edit_event() {
+    let env = Env::default();
+    let (client, _organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let co_organizer = Address::generate(&env);
+    client.grant_role(&event_id, &co_organizer, &Role::CoOrganizer);
+
+    client.update_event(
+        &co_organizer,
+        &event_id,
+        &Some(String::from_str(&env, "Renamed")),
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+
+    let event = client.get_event(&event_id);
+    assert_eq!(event.theme, String::from_str(&env, "Renamed"));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_co_organizer_cannot_cancel_event() {
+    let env = Env::default();
+    let (client, _organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let co_organizer = Address::generate(&env);
+    client.grant_role(&event_id, &co_organizer, &Role::CoOrganizer);
+
+    client.cancel_event(&co_organizer, &event_id);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_finance_cannot_edit_event() {
+    let env = Env::default();
+    let (client, _organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let finance = Address::generate(&env);
+    client.grant_role(&event_id, &finance, &Role::Finance);
+
+    client.cancel_event(&finance, &event_id);
+}
+
+#[test]
+fn test_finance_withdraws_to_organizer() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    let finance = Address::generate(&env);
+    client.grant_role(&event_id, &finance, &Role::Finance);
+
+    let event = client.get_event(&event_id);
+    env.ledger().set_timestamp(event.end_date);
+    client.withdraw_funds(&finance, &event_id);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&organizer), 100);
+    assert_eq!(token_client.balance(&finance), 0);
+}
+
+/// Escrow event created after a 5% + 2 platform fee was set
+fn setup_fee_escrow_event(env: &Env) -> (EventManagerClient<'_>, Address, Address, u32) {
+    let (client, _organizer, payment_token, _event_id) = setup_escrow_event(env);
+    client.set_platform_fee(&500, &2, &Address::generate(env));
+    let event_id = create_fee_event(env, &client, &payment_token);
+    let organizer = client.get_event(&event_id).organizer;
+
+    (client, organizer, payment_token, event_id)
+}
+
+fn create_fee_event(env: &Env, client: &EventManagerClient, payment_token: &Address) -> u32 {
+    let start_date = env.ledger().timestamp() + 86400;
+    client.create_event(
+        &Address::generate(env),
+        &String::from_str(env, "Fee Event"),
+        &String::from_str(env, "Concert"),
+        &start_date,
+        &(start_date + 86400),
+        &100i128,
+        &10u128,
+        payment_token,
+        &TransferPolicy::Free,
+    )
+}
+
+#[test]
+fn test_platform_fee_split_on_purchase() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, _event_id) = setup_escrow_event(&env);
+    let treasury = Address::generate(&env);
+    client.set_platform_fee(&500, &2, &treasury);
+    let event_id = create_fee_event(&env, &client, &payment_token);
+
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+
+    // 5% of 100 plus a flat fee of 2, held in escrow until the event settles
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&treasury), 0);
+    assert_eq!(token_client.balance(&buyer), 900);
+    assert_eq!(client.get_event_balance(&event_id), 93);
+    assert_eq!(client.get_event_fees(&event_id), 7);
+    assert_eq!(client.get_ticket_payment(&event_id, &1u128), Some(100));
+
+    let event = client.get_event(&event_id);
+    env.ledger().set_timestamp(event.end_date);
+    assert_eq!(client.withdraw_funds(&event.organizer, &event_id), 93);
+    assert_eq!(token_client.balance(&treasury), 7);
+    assert_eq!(token_client.balance(&event.organizer), 93);
+    assert_eq!(client.get_event_fees(&event_id), 0);
+}
+
+#[test]
+fn test_platform_fee_only_applies_to_new_events() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let treasury = Address::generate(&env);
+    client.set_platform_fee(&500, &0, &treasury);
+
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&treasury), 0);
+    assert_eq!(client.get_event_balance(&event_id), 100);
+    assert_eq!(client.get_event_fee(&event_id), None);
+}
+
+#[test]
+fn test_cleared_platform_fee_only_applies_to_new_events() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, fee_event) = setup_fee_escrow_event(&env);
+    client.clear_platform_fee();
+    assert_eq!(client.get_platform_fee(), None);
+    let event_id = create_fee_event(&env, &client, &payment_token);
+
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    assert_eq!(client.get_event_fee(&event_id), None);
+    assert_eq!(client.get_event_balance(&event_id), 100);
+    assert_eq!(client.get_event_fees(&event_id), 0);
+    assert!(client.get_event_fee(&fee_event).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Fee exceeds maximum")]
+fn test_platform_fee_is_capped() {
+    let env = Env::default();
+    let (client, _organizer, _payment_token, _event_id) = setup_escrow_event(&env);
+
+    client.set_platform_fee(&1_001, &0, &Address::generate(&env));
+}
+
+fn split_between(env: &Env, artist: &Address, venue: &Address) -> Vec<RevenueSplit> {
+    soroban_sdk::vec![
+        env,
+        RevenueSplit {
+            payee: artist.clone(),
+            bps: 7_000,
+        },
+        RevenueSplit {
+            payee: venue.clone(),
+            bps: 3_000,
+        },
+    ]
+}
+
+#[test]
+fn test_withdraw_distributes_revenue_splits() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let artist = Address::generate(&env);
+    let venue = Address::generate(&env);
+    client.set_revenue_splits(&event_id, &split_between(&env, &artist, &venue));
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    env.ledger()
+        .set_timestamp(client.get_event(&event_id).end_date);
+    let withdrawn = client.withdraw_funds(&organizer, &event_id);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(withdrawn, 200);
+    assert_eq!(token_client.balance(&artist), 140);
+    assert_eq!(token_client.balance(&venue), 60);
+    assert_eq!(token_client.balance(&organizer), 0);
+    assert_eq!(client.get_event_balance(&event_id), 0);
+}
+
+#[test]
+fn test_payees_claim_revenue_share_independently() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let artist = Address::generate(&env);
+    let venue = Address::generate(&env);
+    client.set_revenue_splits(&event_id, &split_between(&env, &artist, &venue));
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    env.ledger()
+        .set_timestamp(client.get_event(&event_id).end_date);
+    assert_eq!(client.claim_revenue_share(&venue, &event_id), 30);
+    assert_eq!(client.get_revenue_paid(&event_id, &venue), 30);
+
+    // The remaining withdrawal only pays the artist
+    assert_eq!(client.withdraw_funds(&organizer, &event_id), 70);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&artist), 70);
+    assert_eq!(token_client.balance(&venue), 30);
+}
+
+#[test]
+#[should_panic(expected = "Revenue splits are locked")]
+fn test_revenue_splits_locked_after_first_sale() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    let splits = split_between(&env, &Address::generate(&env), &Address::generate(&env));
+    client.set_revenue_splits(&event_id, &splits);
+}
+
+#[test]
+#[should_panic(expected = "Split shares must sum to 10000")]
+fn test_revenue_splits_must_sum_to_total() {
+    let env = Env::default();
+    let (client, _organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let splits = soroban_sdk::vec![
+        &env,
+        RevenueSplit {
+            payee: Address::generate(&env),
+            bps: 9_000,
+        },
+    ];
+
+    client.set_revenue_splits(&event_id, &splits);
+}
+
+#[test]
+fn test_resale_pays_seller_and_royalty() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.set_resale_policy(&organizer, &event_id, &1_000, &Some(11_000));
+    let seller = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&seller, &event_id, &0, &None);
+
+    client.list_ticket(&seller, &event_id, &1u128, &110);
+    assert_eq!(
+        client.get_listing(&event_id, &1u128),
+        Some(Listing {
+            seller: seller.clone(),
+            price: 110
+        })
+    );
+
+    let buyer = fund_buyer(&env, &payment_token);
+    client.buy_listed_ticket(&buyer, &event_id, &1u128);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&buyer), 890);
+    assert_eq!(token_client.balance(&seller), 999);
+    assert_eq!(token_client.balance(&organizer), 11);
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    assert_eq!(nft.owner_of(&1u128), buyer);
+    assert_eq!(client.get_listing(&event_id, &1u128), None);
+}
+
+#[test]
+#[should_panic(expected = "Price exceeds resale cap")]
+fn test_resale_price_cap_enforced() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.set_resale_policy(&organizer, &event_id, &0, &Some(11_000));
+    let seller = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&seller, &event_id, &0, &None);
+
+    client.list_ticket(&seller, &event_id, &1u128, &111);
+}
+
+#[test]
+#[should_panic(expected = "Price exceeds resale cap")]
+fn test_resale_cap_uses_price_paid() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.set_resale_policy(&organizer, &event_id, &0, &Some(10_000));
+    let seller = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&seller, &event_id, &0, &None);
+
+    // Raising the tier price after the sale doesn't loosen the cap
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &Some(250i128),
+        &None,
+        &None,
+        &None,
+    );
+
+    client.list_ticket(&seller, &event_id, &1u128, &101);
+}
+
+#[test]
+#[should_panic(expected = "Price exceeds resale cap")]
+fn test_resale_cap_rechecked_on_purchase() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    client.set_resale_policy(&organizer, &event_id, &0, &Some(20_000));
+    let seller = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&seller, &event_id, &0, &None);
+    client.list_ticket(&seller, &event_id, &1u128, &150);
+
+    client.set_resale_policy(&organizer, &event_id, &0, &Some(10_000));
+
+    let buyer = fund_buyer(&env, &payment_token);
+    client.buy_listed_ticket(&buyer, &event_id, &1u128);
+}
+
+#[test]
+fn test_listing_canceled_when_ticket_checked_in() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let seller = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&seller, &event_id, &0, &None);
+    client.list_ticket(&seller, &event_id, &1u128, &100);
+
+    start_event(&env, &client, event_id);
+    client.check_in(&organizer, &event_id, &1u128);
+
+    assert_eq!(client.get_listing(&event_id, &1u128), None);
+}
+
+#[test]
+fn test_listing_inactive_after_peer_transfer() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let seller = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&seller, &event_id, &0, &None);
+    client.list_ticket(&seller, &event_id, &1u128, &100);
+
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    nft.transfer_from(&seller, &seller, &Address::generate(&env), &1u128);
+
+    assert_eq!(client.get_listing(&event_id, &1u128), None);
+}
+
+#[test]
+fn test_approve_ticket_transfer() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+    let recipient = Address::generate(&env);
+
+    client.approve_ticket_transfer(&organizer, &event_id, &1u128, &recipient);
+
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+    assert_eq!(nft.get_transfer_approval(&1u128), Some(recipient));
+}
+
+#[test]
+fn test_transfer_lock_window_follows_event_dates() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    let nft = MockContractClient::new(&env, &client.get_event(&event_id).ticket_nft_addr);
+
+    client.set_transfer_lock_window(&organizer, &event_id, &Some(3600));
+    let event = client.get_event(&event_id);
+    assert_eq!(
+        nft.transfer_lock(),
+        Some((event.start_date - 3600, event.end_date))
+    );
+
+    let new_start = event.start_date + 86400;
+    client.reschedule_event(&organizer, &event_id, &new_start, &(new_start + 7200));
+    assert_eq!(
+        nft.transfer_lock(),
+        Some((new_start - 3600, new_start + 7200))
+    );
+
+    client.set_transfer_lock_window(&organizer, &event_id, &None);
+    assert_eq!(client.get_transfer_lock_window(&event_id), None);
+    assert_eq!(nft.transfer_lock(), None);
+}
+
+fn setup_sold_out_event(env: &Env) -> (EventManagerClient<'_>, Address, Address, u32) {
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(env);
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &None,
+        &Some(1u128),
+        &None,
+        &None,
+    );
+    client.purchase_ticket(&fund_buyer(env, &payment_token), &event_id, &0, &None);
+    (client, organizer, payment_token, event_id)
+}
+
+#[test]
+fn test_waitlist_claims_freed_ticket() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_sold_out_event(&env);
+    let user = fund_buyer(&env, &payment_token);
+    let token_client = token::Client::new(&env, &payment_token);
+
+    client.join_waitlist(&user, &event_id, &0, &true);
+    assert_eq!(token_client.balance(&user), 900);
+    assert_eq!(client.get_waitlist_length(&event_id), 1);
+    assert_eq!(client.get_waitlist_position(&event_id, &user), Some(1));
+
+    // The freed seat is held for the waitlist
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &None,
+        &Some(2u128),
+        &None,
+        &None,
+    );
+    let entry = client.get_waitlist_entry(&event_id, &user).unwrap();
+    assert!(entry.offer_expires_at.is_some());
+    let result =
+        client.try_purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    assert!(result.is_err());
+
+    client.claim_waitlist_ticket(&user, &event_id);
+
+    assert_eq!(token_client.balance(&user), 900);
+    assert_eq!(client.get_event(&event_id).tickets_sold, 2);
+    assert_eq!(client.get_waitlist_length(&event_id), 0);
+    assert_eq!(client.get_waitlist_position(&event_id, &user), None);
+}
+
+#[test]
+fn test_waitlist_offer_passes_to_next_user_after_expiry() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_sold_out_event(&env);
+    let first = fund_buyer(&env, &payment_token);
+    let second = fund_buyer(&env, &payment_token);
+    client.join_waitlist(&first, &event_id, &0, &true);
+    client.join_waitlist(&second, &event_id, &0, &false);
+    assert_eq!(client.get_waitlist_position(&event_id, &second), Some(2));
+
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &None,
+        &Some(2u128),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client
+            .get_waitlist_entry(&event_id, &second)
+            .unwrap()
+            .offer_expires_at,
+        None
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 24 * 60 * 60 + 1);
+    client.process_waitlist(&event_id);
+
+    // The first user's deposit is returned and the offer moves on
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&first), 1000);
+    assert_eq!(client.get_waitlist_position(&event_id, &first), None);
+    assert_eq!(client.get_waitlist_position(&event_id, &second), Some(1));
+    assert!(client
+        .get_waitlist_entry(&event_id, &second)
+        .unwrap()
+        .offer_expires_at
+        .is_some());
+}
+
+#[test]
+fn test_resize_after_burns_with_real_ticket_nft() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(real_nft::ticket_nft_contract::WASM);
+    let factory = env.register(real_nft::NftFactory, (&wasm_hash,));
+    let client = EventManagerClient::new(&env, &env.register(EventManager, ()));
+    client.initialize(&factory, &Address::generate(&env));
+
+    let payment_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let organizer = Address::generate(&env);
+    let start_date = env.ledger().timestamp() + 86400;
+    let event_id = client.create_event(
+        &organizer,
+        &String::from_str(&env, "Real NFT Event"),
+        &String::from_str(&env, "Concert"),
+        &start_date,
+        &(start_date + 86400),
+        &100i128,
+        &2u128,
+        &payment_token,
+        &TransferPolicy::Free,
+    );
+    let nft = real_nft::ticket_nft_contract::Client::new(
+        &env,
+        &client.get_event(&event_id).ticket_nft_addr,
+    );
+
+    // Sell out, burn everything and sell out again: four tickets minted, two live
+    for token_id in 1..=2u128 {
+        client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+        client.burn_ticket(&event_id, &token_id);
+    }
+    for _ in 0..2 {
+        client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+    }
+    assert_eq!(nft.total_supply(), 2);
+
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &None,
+        &Some(3u128),
+        &None,
+        &None,
+    );
+    assert_eq!(nft.max_supply(), 3);
+
+    client.update_event(
+        &organizer,
+        &event_id,
+        &None,
+        &None,
+        &Some(2u128),
+        &None,
+        &None,
+    );
+    assert_eq!(nft.max_supply(), 2);
+}
+
+#[test]
+fn test_burned_ticket_frees_seat_for_waitlist() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_sold_out_event(&env);
+    let user = fund_buyer(&env, &payment_token);
+    client.join_waitlist(&user, &event_id, &0, &false);
+
+    client.burn_ticket(&event_id, &1u128);
+
+    let event = client.get_event(&event_id);
+    assert_eq!(event.tickets_sold, 0);
+    assert_eq!(client.get_ticket_tier(&event_id, &0).sold, 0);
+    let nft = MockContractClient::new(&env, &event.ticket_nft_addr);
+    assert!(!nft.is_valid(&1u128));
+    assert!(client
+        .get_waitlist_entry(&event_id, &user)
+        .unwrap()
+        .offer_expires_at
+        .is_some());
+
+    client.claim_waitlist_ticket(&user, &event_id);
+    assert_eq!(client.get_event(&event_id).tickets_sold, 1);
+}
+
+#[test]
+fn test_waitlist_offers_freed_seat_to_user_waiting_for_its_tier() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let mut tiers = Vec::new(&env);
+    for name in ["VIP", "Balcony"] {
+        let tier_id = client.create_ticket_tier(
+            &organizer,
+            &event_id,
+            &String::from_str(&env, name),
+            &300i128,
+            &1u128,
+            &None,
+            &None,
+        );
+        client.purchase_ticket(
+            &fund_buyer(&env, &payment_token),
+            &event_id,
+            &tier_id,
+            &None,
+        );
+        tiers.push_back(tier_id);
+    }
+    let (vip_tier, balcony_tier) = (tiers.get(0).unwrap(), tiers.get(1).unwrap());
+
+    let vip_user = fund_buyer(&env, &payment_token);
+    let balcony_user = fund_buyer(&env, &payment_token);
+    client.join_waitlist(&vip_user, &event_id, &vip_tier, &false);
+    client.join_waitlist(&balcony_user, &event_id, &balcony_tier, &false);
+    assert_eq!(
+        client.get_waitlist_position(&event_id, &balcony_user),
+        Some(2)
+    );
+
+    // A balcony seat frees up while VIP stays sold out
+    client.update_ticket_tier(
+        &organizer,
+        &event_id,
+        &balcony_tier,
+        &None,
+        &None,
+        &Some(2u128),
+        &None,
+        &None,
+    );
+    client.process_waitlist(&event_id);
+
+    let vip_entry = client.get_waitlist_entry(&event_id, &vip_user).unwrap();
+    assert_eq!(vip_entry.offer_expires_at, None);
+    let balcony_entry = client.get_waitlist_entry(&event_id, &balcony_user).unwrap();
+    assert!(balcony_entry.offer_expires_at.is_some());
+
+    // The balcony seat is held for the waitlist, other tiers stay on sale
+    let result = client.try_purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &balcony_tier,
+        &None,
+    );
+    assert!(result.is_err());
+    client.purchase_ticket(&fund_buyer(&env, &payment_token), &event_id, &0, &None);
+
+    client.claim_waitlist_ticket(&balcony_user, &event_id);
+    assert_eq!(client.get_ticket_tier(&event_id, &balcony_tier).sold, 2);
+    assert_eq!(client.get_waitlist_position(&event_id, &vip_user), Some(1));
+}
+
+#[test]
+#[should_panic(expected = "Tickets are still available")]
+fn test_cannot_join_waitlist_when_tickets_available() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_escrow_event(&env);
+
+    client.join_waitlist(&fund_buyer(&env, &payment_token), &event_id, &0, &false);
+}
+
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a < b { (a, b) } else { (b, a) };
+    let mut pair = Bytes::new(env);
+    pair.append(&first.clone().into());
+    pair.append(&second.clone().into());
+    env.crypto().sha256(&pair).into()
+}
+
+/// Build a merkle tree over `members`, returning the root and each member's proof
+fn allowlist_tree(env: &Env, members: &[Address]) -> (BytesN<32>, std::vec::Vec<Vec<BytesN<32>>>) {
+    let mut level: std::vec::Vec<BytesN<32>> = members
+        .iter()
+        .map(|member| env.crypto().sha256(&member.clone().to_xdr(env)).into())
+        .collect();
+    let mut positions: std::vec::Vec<usize> = (0..members.len()).collect();
+    let mut proofs: std::vec::Vec<Vec<BytesN<32>>> =
+        members.iter().map(|_| Vec::new(env)).collect();
+
+    while level.len() > 1 {
+        for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+            let sibling = *position ^ 1;
+            if sibling < level.len() {
+                proof.push_back(level[sibling].clone());
+            }
+            *position /= 2;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(env, a, b),
+                [a] => a.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    (level[0].clone(), proofs)
+}
+
+fn setup_presale(
+    env: &Env,
+) -> (
+    EventManagerClient<'_>,
+    Address,
+    std::vec::Vec<Address>,
+    std::vec::Vec<Vec<BytesN<32>>>,
+    u32,
+) {
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(env);
+    let members: std::vec::Vec<Address> = (0..5).map(|_| fund_buyer(env, &payment_token)).collect();
+    let (merkle_root, proofs) = allowlist_tree(env, &members);
+
+    let now = env.ledger().timestamp();
+    client.set_presale(
+        &organizer,
+        &event_id,
+        &PresaleConfig {
+            merkle_root,
+            start: now,
+            end: now + 3600,
+            price: Some(80),
+            public_sale_start: now + 7200,
+        },
+    );
+
+    (client, payment_token, members, proofs, event_id)
+}
+
+#[test]
+fn test_presale_members_buy_at_presale_price() {
+    let env = Env::default();
+    let (client, payment_token, members, proofs, event_id) = setup_presale(&env);
+
+    for (member, proof) in members.iter().zip(proofs.iter()) {
+        client.purchase_ticket(
+            member,
+            &event_id,
+            &0,
+            &Some(PurchaseOptions {
+                proof: Some(proof.clone()),
+                ..Default::default()
+            }),
+        );
+    }
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&members[4]), 920);
+    assert_eq!(client.get_event(&event_id).tickets_sold, 5);
+}
+
+#[test]
+#[should_panic(expected = "Not on the presale allowlist")]
+fn test_presale_rejects_proof_for_other_buyer() {
+    let env = Env::default();
+    let (client, payment_token, _members, proofs, event_id) = setup_presale(&env);
+    let outsider = fund_buyer(&env, &payment_token);
+
+    client.purchase_ticket(
+        &outsider,
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            proof: Some(proofs[0].clone()),
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn test_public_sale_opens_after_presale() {
+    let env = Env::default();
+    let (client, payment_token, _members, _proofs, event_id) = setup_presale(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+
+    // Closed between the presale and the public sale
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    let result = client.try_purchase_ticket(&buyer, &event_id, &0, &None);
+    assert!(result.is_err());
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&buyer), 900);
+}
+
+#[test]
+fn test_price_steps_follow_ledger_time() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+
+    let now = env.ledger().timestamp();
+    client.set_price_schedule(
+        &organizer,
+        &event_id,
+        &Some(PriceSchedule::Steps(vec![
+            &env,
+            PriceStep {
+                starts_at: now + 100,
+                price: 120,
+            },
+            PriceStep {
+                starts_at: now + 200,
+                price: 150,
+            },
+        ])),
+    );
+
+    // Tier price applies before the first step
+    assert_eq!(client.current_price(&event_id), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = now + 100);
+    assert_eq!(client.current_price(&event_id), 120);
+
+    env.ledger().with_mut(|li| li.timestamp = now + 250);
+    assert_eq!(client.current_price(&event_id), 150);
+
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&buyer, &event_id, &0, &None);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&buyer), 850);
+    assert_eq!(client.get_event_balance(&event_id), 150);
+}
+
+#[test]
+fn test_linear_price_ramp() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    let now = env.ledger().timestamp();
+    client.set_price_schedule(
+        &organizer,
+        &event_id,
+        &Some(PriceSchedule::Linear(PriceRamp {
+            start_time: now + 1000,
+            end_time: now + 2000,
+            start_price: 50,
+            end_price: 150,
+        })),
+    );
+
+    assert_eq!(client.current_price(&event_id), 50);
+
+    env.ledger().with_mut(|li| li.timestamp = now + 1250);
+    assert_eq!(client.current_price(&event_id), 75);
+
+    env.ledger().with_mut(|li| li.timestamp = now + 5000);
+    assert_eq!(client.current_price(&event_id), 150);
+
+    // Removing the schedule restores the tier price
+    client.set_price_schedule(&organizer, &event_id, &None);
+    assert_eq!(client.current_price(&event_id), 100);
+}
+
+#[test]
+#[should_panic(expected = "Price steps must be in chronological order")]
+fn test_price_steps_must_be_ordered() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    client.set_price_schedule(
+        &organizer,
+        &event_id,
+        &Some(PriceSchedule::Steps(vec![
+            &env,
+            PriceStep {
+                starts_at: 200,
+                price: 120,
+            },
+            PriceStep {
+                starts_at: 100,
+                price: 150,
+            },
+        ])),
+    );
+}
+
+#[test]
+fn test_linear_bonding_curve_prices_by_tickets_sold() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+
+    client.set_bonding_curve(
+        &organizer,
+        &event_id,
+        &Some(BondingCurve::Linear(LinearCurve {
+            base_price: 100,
+            step: 10,
+            max_price: 125,
+        })),
+    );
+
+    assert_eq!(client.current_price(&event_id), 100);
+    assert_eq!(client.quote(&event_id, &4), 100 + 110 + 120 + 125);
+
+    let buyer = fund_buyer(&env, &payment_token);
+    let recipients = vec![&env, buyer.clone(), Address::generate(&env)];
+    client.purchase_tickets(
+        &buyer,
+        &event_id,
+        &0,
+        &recipients,
+        &Some(PurchaseOptions {
+            max_price: Some(210),
+            ..Default::default()
+        }),
+    );
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&buyer), 1000 - 210);
+    assert_eq!(client.current_price(&event_id), 120);
+}
+
+#[test]
+fn test_exponential_bonding_curve_is_capped() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    client.set_bonding_curve(
+        &organizer,
+        &event_id,
+        &Some(BondingCurve::Exponential(ExponentialCurve {
+            base_price: 1_000,
+            growth_bps: 1_000,
+            max_price: 1_300,
+        })),
+    );
+
+    // 1000, 1100, 1210, then capped at 1300
+    assert_eq!(client.quote(&event_id, &4), 1_000 + 1_100 + 1_210 + 1_300);
+}
+
+#[test]
+#[should_panic(expected = "Quote overflow")]
+fn test_quote_overflow_fails() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+    client.update_ticket_tier(
+        &organizer,
+        &event_id,
+        &0,
+        &None,
+        &Some(i128::MAX),
+        &None,
+        &None,
+        &None,
+    );
+
+    client.quote(&event_id, &2);
+}
+
+#[test]
+#[should_panic(expected = "Price exceeds max price")]
+fn test_purchase_rejects_price_above_max_price() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+
+    client.set_bonding_curve(
+        &organizer,
+        &event_id,
+        &Some(BondingCurve::Linear(LinearCurve {
+            base_price: 100,
+            step: 10,
+            max_price: 500,
+        })),
+    );
+
+    let first = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(
+        &first,
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            max_price: Some(100),
+            ..Default::default()
+        }),
+    );
+
+    // The price moved to 110 after the first sale
+    let second = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(
+        &second,
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            max_price: Some(100),
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Curve growth exceeds maximum")]
+fn test_bonding_curve_growth_is_bounded() {
+    let env = Env::default();
+    let (client, organizer, _payment_token, event_id) = setup_escrow_event(&env);
+
+    client.set_bonding_curve(
+        &organizer,
+        &event_id,
+        &Some(BondingCurve::Exponential(ExponentialCurve {
+            base_price: 100,
+            growth_bps: 5_000,
+            max_price: 1_000,
+        })),
+    );
+}
+
+fn setup_auction(env: &Env) -> (EventManagerClient<'_>, Address, Address, u32) {
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(env);
+    let vip_tier = client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(env, "VIP"),
+        &300i128,
+        &2u128,
+        &None,
+        &None,
+    );
+
+    let now = env.ledger().timestamp();
+    client.set_dutch_auction(
+        &organizer,
+        &event_id,
+        &DutchAuction {
+            tier_id: vip_tier,
+            start_time: now,
+            end_time: now + 3600,
+            start_price: 500,
+            floor_price: 200,
+            decay_period: 1000,
+        },
+    );
+
+    (client, organizer, payment_token, event_id)
+}
+
+#[test]
+fn test_dutch_auction_bidders_pay_clearing_price() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_auction(&env);
+    let early = fund_buyer(&env, &payment_token);
+    let late = fund_buyer(&env, &payment_token);
+
+    assert_eq!(client.place_bid(&early, &event_id), 500);
+
+    env.ledger().with_mut(|li| li.timestamp += 500);
+    assert_eq!(client.auction_price(&event_id), 350);
+    assert_eq!(client.place_bid(&late, &event_id), 350);
+
+    // Sold out, so the auction can settle before it ends
+    assert_eq!(client.settle_auction(&event_id), 350);
+
+    client.claim_auction_ticket(&early, &event_id);
+    client.claim_auction_ticket(&late, &event_id);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&early), 650);
+    assert_eq!(token_client.balance(&late), 650);
+    assert_eq!(client.get_event_balance(&event_id), 700);
+    assert_eq!(client.get_event(&event_id).tickets_sold, 2);
+}
+
+#[test]
+fn test_dutch_auction_settles_after_end() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_auction(&env);
+    let bidder = fund_buyer(&env, &payment_token);
+
+    env.ledger().with_mut(|li| li.timestamp += 2000);
+    assert_eq!(client.place_bid(&bidder, &event_id), 200);
+
+    let result = client.try_settle_auction(&event_id);
+    assert!(result.is_err());
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    assert_eq!(client.settle_auction(&event_id), 200);
+    assert!(client.claim_auction_ticket(&bidder, &event_id).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Tier is sold by auction")]
+fn test_auction_tier_cannot_be_bought_directly() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_auction(&env);
+    let buyer = fund_buyer(&env, &payment_token);
+
+    client.purchase_ticket(&buyer, &event_id, &1, &None);
+}
+
+#[test]
+fn test_canceled_auction_refunds_bids() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_auction(&env);
+    let bidder = fund_buyer(&env, &payment_token);
+
+    client.place_bid(&bidder, &event_id);
+    client.cancel_event(&organizer, &event_id);
+
+    assert_eq!(client.claim_auction_ticket(&bidder, &event_id), None);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&bidder), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Wallet ticket limit reached")]
+fn test_auction_bid_requires_wallet_room() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_auction(&env);
+    let bidder = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&bidder, &event_id, &0, &None);
+
+    client.place_bid(&bidder, &event_id);
+}
+
+#[test]
+fn test_auction_winner_with_full_wallet_is_refunded() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_auction(&env);
+    let bidder = fund_buyer(&env, &payment_token);
+    client.place_bid(&bidder, &event_id);
+    client.purchase_ticket(&bidder, &event_id, &0, &None);
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.settle_auction(&event_id);
+    assert_eq!(client.get_event_balance(&event_id), 600);
+
+    assert_eq!(client.claim_auction_ticket(&bidder, &event_id), None);
+
+    // The whole bid comes back and the seat is released
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&bidder), 900);
+    assert_eq!(client.get_event_balance(&event_id), 100);
+    assert_eq!(client.get_ticket_tier(&event_id, &1).sold, 0);
+}
+
+#[test]
+#[should_panic(expected = "Revenue splits are locked")]
+fn test_auction_bid_locks_revenue_splits() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id) = setup_auction(&env);
+    client.place_bid(&fund_buyer(&env, &payment_token), &event_id);
+
+    let artist = Address::generate(&env);
+    let venue = Address::generate(&env);
+    client.set_revenue_splits(&event_id, &split_between(&env, &artist, &venue));
+}
+
+fn lottery_secret(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[7; 32])
+}
+
+fn setup_lottery(
+    env: &Env,
+    entrants: u32,
+) -> (EventManagerClient<'_>, Address, std::vec::Vec<Address>, u32) {
+    setup_lottery_with_secret(env, entrants, &lottery_secret(env))
+}
+
+fn setup_lottery_with_secret<'a>(
+    env: &'a Env,
+    entrants: u32,
+    secret: &BytesN<32>,
+) -> (EventManagerClient<'a>, Address, std::vec::Vec<Address>, u32) {
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(env);
+    let vip_tier = client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(env, "VIP"),
+        &300i128,
+        &2u128,
+        &None,
+        &None,
+    );
+
+    let close_ledger = env.ledger().sequence() + 100;
+    client.set_lottery(
+        &organizer,
+        &event_id,
+        &LotteryConfig {
+            tier_id: vip_tier,
+            close_ledger,
+            seed_commitment: env.crypto().sha256(&secret.clone().into()).into(),
+        },
+    );
+
+    let users: std::vec::Vec<Address> = (0..entrants)
+        .map(|_| {
+            let user = fund_buyer(env, &payment_token);
+            client.enter_lottery(&user, &event_id);
+            user
+        })
+        .collect();
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = close_ledger);
+
+    (client, payment_token, users, event_id)
+}
+
+#[test]
+fn test_lottery_draws_winners_up_to_supply() {
+    let env = Env::default();
+    let (client, payment_token, users, event_id) = setup_lottery(&env, 5);
+
+    assert_eq!(client.draw_lottery(&event_id, &lottery_secret(&env)), 2);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    let mut winners = 0;
+    for user in users.iter() {
+        let won = client.is_lottery_winner(&event_id, user);
+        let token_id = client.claim_lottery(user, &event_id);
+        assert_eq!(token_id.is_some(), won);
+        if won {
+            winners += 1;
+            assert_eq!(token_client.balance(user), 700);
+        } else {
+            assert_eq!(token_client.balance(user), 1000);
+        }
+    }
+
+    assert_eq!(winners, 2);
+    assert_eq!(client.get_event_balance(&event_id), 600);
+    assert_eq!(client.get_ticket_tier(&event_id, &1).sold, 2);
+}
+
+#[test]
+fn test_lottery_everyone_wins_when_undersubscribed() {
+    let env = Env::default();
+    let (client, _payment_token, users, event_id) = setup_lottery(&env, 2);
+
+    assert_eq!(client.draw_lottery(&event_id, &lottery_secret(&env)), 2);
+    for user in users.iter() {
+        assert!(client.is_lottery_winner(&event_id, user));
+    }
+}
+
+#[test]
+fn test_lottery_draw_is_reproducible_from_seed() {
+    let env = Env::default();
+    let (client, _payment_token, users, event_id) = setup_lottery(&env, 6);
+    let winners = client.draw_lottery(&event_id, &lottery_secret(&env));
+
+    // The seed is derived from the revealed secret and on-chain data
+    let state = client.get_lottery_state(&event_id).unwrap();
+    let config = client.get_lottery(&event_id).unwrap();
+    let seed: BytesN<32> = env
+        .crypto()
+        .sha256(
+            &(
+                state.secret.unwrap(),
+                client.address.clone(),
+                event_id,
+                config.close_ledger,
+                6u32,
+            )
+                .to_xdr(&env),
+        )
+        .into();
+    assert_eq!(state.seed, Some(seed.clone()));
+
+    // Re-running the shuffle with the seed picks the same winners
+    let mut indices = vec![&env, 0u32, 1, 2, 3, 4, 5];
+    env.as_contract(&client.address, || {
+        env.prng().seed(seed.into());
+        env.prng().shuffle(&mut indices);
+    });
+
+    for (index, user) in users.iter().enumerate() {
+        let expected = indices
+            .iter()
+            .take(winners as usize)
+            .any(|winner| winner == index as u32);
+        assert_eq!(client.is_lottery_winner(&event_id, user), expected);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Secret does not match the seed commitment")]
+fn test_lottery_draw_requires_committed_secret() {
+    let env = Env::default();
+    let (client, _payment_token, _users, event_id) = setup_lottery(&env, 3);
+
+    client.draw_lottery(&event_id, &BytesN::from_array(&env, &[8; 32]));
+}
+
+#[test]
+fn test_lottery_winners_are_uniformly_distributed() {
+    const DRAWS: u8 = 200;
+    const ENTRANTS: usize = 4;
+
+    let mut wins = [0u32; ENTRANTS];
+    for draw in 0..DRAWS {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        let secret = BytesN::from_array(&env, &[draw; 32]);
+
+        let (client, _payment_token, users, event_id) =
+            setup_lottery_with_secret(&env, ENTRANTS as u32, &secret);
+        client.draw_lottery(&event_id, &secret);
+        for (index, user) in users.iter().enumerate() {
+            if client.is_lottery_winner(&event_id, user) {
+                wins[index] += 1;
+            }
+        }
+    }
+
+    // Two of four seats: each entrant should win about half the draws
+    for count in wins {
+        assert!((70..=130).contains(&count), "{wins:?}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "Wallet ticket limit reached")]
+fn test_lottery_entry_requires_wallet_room() {
+    let env = Env::default();
+    let (client, payment_token, _users, event_id) = setup_lottery(&env, 0);
+    env.ledger().with_mut(|li| li.sequence_number -= 1);
+
+    let user = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(&user, &event_id, &0, &None);
+    client.enter_lottery(&user, &event_id);
+}
+
+#[test]
+fn test_lottery_winner_with_full_wallet_is_refunded() {
+    let env = Env::default();
+    let (client, payment_token, users, event_id) = setup_lottery(&env, 1);
+    client.purchase_ticket(&users[0], &event_id, &0, &None);
+    client.draw_lottery(&event_id, &lottery_secret(&env));
+    assert_eq!(client.get_ticket_tier(&event_id, &1).sold, 1);
+
+    assert_eq!(client.claim_lottery(&users[0], &event_id), None);
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&users[0]), 900);
+    assert_eq!(client.get_ticket_tier(&event_id, &1).sold, 0);
+    assert_eq!(client.get_event(&event_id).tickets_sold, 1);
+}
+
+#[test]
+fn test_lottery_registration_closes_at_ledger() {
+    let env = Env::default();
+    let (client, payment_token, _users, event_id) = setup_lottery(&env, 1);
+    let late = fund_buyer(&env, &payment_token);
+
+    let result = client.try_enter_lottery(&late, &event_id);
+    assert!(result.is_err());
+
+    client.draw_lottery(&event_id, &lottery_secret(&env));
+    let result = client.try_draw_lottery(&event_id, &lottery_secret(&env));
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Lottery has not been drawn")]
+fn test_lottery_claim_requires_draw() {
+    let env = Env::default();
+    let (client, _payment_token, users, event_id) = setup_lottery(&env, 1);
+
+    client.claim_lottery(&users[0], &event_id);
+}
+
+fn add_promo(
+    env: &Env,
+    client: &EventManagerClient<'_>,
+    organizer: &Address,
+    event_id: u32,
+    code: &str,
+    promo: PromoCode,
+) -> Bytes {
+    let code = Bytes::from_slice(env, code.as_bytes());
+    let code_hash: BytesN<32> = env.crypto().sha256(&code).into();
+    client.add_promo_code(organizer, &event_id, &code_hash, &promo);
+    code
+}
+
+#[test]
+fn test_promo_code_applies_percentage_discount() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let code = add_promo(
+        &env,
+        &client,
+        &organizer,
+        event_id,
+        "EARLYBIRD",
+        PromoCode {
+            discount: Discount::Percentage(2_500),
+            remaining_uses: 5,
+            expires_at: None,
+            tier_id: None,
+        },
+    );
+
+    let buyer = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(
+        &buyer,
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            promo_code: Some(code.clone()),
+            ..Default::default()
+        }),
+    );
+
+    let code_hash: BytesN<32> = env.crypto().sha256(&code).into();
+    let redeemed = (
+        client.address.clone(),
+        (Symbol::new(&env, "promo_code_redeemed"), event_id).into_val(&env),
+        (code_hash.clone(), buyer.clone(), 1u32, 25i128).into_val(&env),
+    );
+    assert!(env.events().all().contains(redeemed));
+
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&buyer), 925);
+    assert_eq!(client.get_event_balance(&event_id), 75);
+    assert_eq!(
+        client
+            .get_promo_code(&event_id, &code_hash)
+            .unwrap()
+            .remaining_uses,
+        4
+    );
+}
+
+#[test]
+#[should_panic(expected = "Promo code has no uses left")]
+fn test_promo_code_uses_are_limited() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(&env);
+    let code = add_promo(
+        &env,
+        &client,
+        &organizer,
+        event_id,
+        "FRIENDS",
+        PromoCode {
+            discount: Discount::Fixed(30),
+            remaining_uses: 1,
+            expires_at: None,
+            tier_id: None,
+        },
+    );
+
+    let first = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(
+        &first,
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            promo_code: Some(code.clone()),
+            ..Default::default()
+        }),
+    );
+    let token_client = token::Client::new(&env, &payment_token);
+    assert_eq!(token_client.balance(&first), 930);
+
+    let second = fund_buyer(&env, &payment_token);
+    client.purchase_ticket(
+        &second,
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            promo_code: Some(code),
+            ..Default::default()
+        }),
+    );
+}
+
+fn setup_ga_only_promo(env: &Env) -> (EventManagerClient<'_>, Address, Address, u32, Bytes) {
+    let (client, organizer, payment_token, event_id) = setup_escrow_event(env);
+    let code = add_promo(
+        env,
+        &client,
+        &organizer,
+        event_id,
+        "GA-ONLY",
+        PromoCode {
+            discount: Discount::Fixed(50),
+            remaining_uses: 10,
+            expires_at: Some(env.ledger().timestamp() + 3600),
+            tier_id: Some(0),
+        },
+    );
+    (client, organizer, payment_token, event_id, code)
+}
+
+#[test]
+#[should_panic(expected = "Promo code is not valid for this tier")]
+fn test_promo_code_restricted_to_tier() {
+    let env = Env::default();
+    let (client, organizer, payment_token, event_id, code) = setup_ga_only_promo(&env);
+    let vip_tier = client.create_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "VIP"),
+        &300i128,
+        &2u128,
+        &None,
+        &None,
+    );
+
+    client.purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &vip_tier,
+        &Some(PurchaseOptions {
+            promo_code: Some(code),
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid promo code")]
+fn test_promo_code_must_match() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id, _code) = setup_ga_only_promo(&env);
+
+    client.purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            promo_code: Some(Bytes::from_slice(&env, b"GA-0NLY")),
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Promo code expired")]
+fn test_promo_code_expires() {
+    let env = Env::default();
+    let (client, _organizer, payment_token, event_id, code) = setup_ga_only_promo(&env);
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.purchase_ticket(
+        &fund_buyer(&env, &payment_token),
+        &event_id,
+        &0,
+        &Some(PurchaseOptions {
+            promo_code: Some(code),
+            ..Default::default()
+        }),
+    );
+}
@@ -54,7 +54,7 @@ fn test_integration_flow() {
     nft_client.initialize(&minter);
 
     // 2. Mint ticket to user
-    let token_id = nft_client.mint_ticket_nft(&user);
+    let token_id = nft_client.mint_ticket_nft(&user, &0);
     assert_eq!(token_id, 1);
     assert_eq!(nft_client.owner_of(&token_id), user);
 
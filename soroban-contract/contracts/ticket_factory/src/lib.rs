@@ -29,6 +29,20 @@ pub enum DataKey {
     TicketContract(u32),
 }
 
+/// Transfer policy of a deployed Ticket NFT contract
+///
+/// Mirrors `TransferPolicy` in the Ticket NFT contract.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    /// Holders can transfer tickets freely
+    Free,
+    /// Tickets can never leave the wallet they were minted to
+    Soulbound,
+    /// Each transfer must be approved by the minter
+    OrganizerApproved,
+}
+
 /// Ticket Factory Contract
 ///
 /// Deploys and tracks Ticket NFT contract instances for events.
@@ -63,13 +77,23 @@ impl TicketFactory {
     /// * `env` - The contract environment
     /// * `minter` - Address that will have minting rights on the new contract
     /// * `salt` - Unique salt for deterministic address generation
+    /// * `max_tickets_per_wallet` - Per-wallet ticket limit for the new contract
+    /// * `max_supply` - Maximum number of tickets the new contract can have in circulation
+    /// * `transfer_policy` - Whether and how tickets of the new contract can be transferred
     ///
     /// # Returns
     /// The address of the newly deployed Ticket NFT contract
     ///
     /// # Authorization
     /// Requires admin authorization
-    pub fn deploy_ticket(env: Env, minter: Address, salt: BytesN<32>) -> Address {
+    pub fn deploy_ticket(
+        env: Env,
+        minter: Address,
+        salt: BytesN<32>,
+        max_tickets_per_wallet: u32,
+        max_supply: u128,
+        transfer_policy: TransferPolicy,
+    ) -> Address {
         // Authorize: only admin can deploy
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
@@ -82,8 +106,15 @@ impl TicketFactory {
             .unwrap();
 
         // Prepare constructor arguments for the Ticket NFT contract
-        // The minter address is passed to initialize the NFT contract
-        let constructor_args: Vec<Val> = (minter.clone(),).into_val(&env);
+        // The minter address, ticket limit, supply cap and transfer policy are passed to
+        // initialize the NFT contract
+        let constructor_args: Vec<Val> = (
+            minter.clone(),
+            max_tickets_per_wallet,
+            max_supply,
+            transfer_policy,
+        )
+            .into_val(&env);
 
         // Deploy using Soroban's deployer pattern
         // This creates a new contract instance with a deterministic address
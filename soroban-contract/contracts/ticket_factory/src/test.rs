@@ -11,7 +11,7 @@
 extern crate alloc;
 extern crate std;
 
-use crate::{TicketFactory, TicketFactoryClient};
+use crate::{TicketFactory, TicketFactoryClient, TransferPolicy};
 use soroban_sdk::{
     testutils::Address as _,
     Address, BytesN, Env,
@@ -50,7 +50,7 @@ fn test_can_deploy_ticket_contract() {
     let salt = BytesN::from_array(&env, &[1u8; 32]);
 
     // Deploy a ticket contract
-    let deployed_address = client.deploy_ticket(&minter, &salt);
+    let deployed_address = client.deploy_ticket(&minter, &salt, &1u32, &100u128, &TransferPolicy::Free);
 
     // Verify the deployed address is valid (not zero)
     assert!(deployed_address != Address::generate(&env));
@@ -68,7 +68,7 @@ fn test_returns_correct_address() {
     let salt = BytesN::from_array(&env, &[2u8; 32]);
 
     // Deploy and store the address
-    let deployed_address = client.deploy_ticket(&minter, &salt);
+    let deployed_address = client.deploy_ticket(&minter, &salt, &1u32, &100u128, &TransferPolicy::Free);
 
     // Retrieve the address using get_ticket_contract
     let retrieved_address = client.get_ticket_contract(&1u32);
@@ -86,7 +86,7 @@ fn test_deployed_contract_has_correct_minter() {
     let salt = BytesN::from_array(&env, &[3u8; 32]);
 
     // Deploy a ticket contract
-    let deployed_address = client.deploy_ticket(&minter, &salt);
+    let deployed_address = client.deploy_ticket(&minter, &salt, &1u32, &100u128, &TransferPolicy::Free);
 
     // Create a client for the deployed contract
     let nft_client = ticket_nft_contract::Client::new(&env, &deployed_address);
@@ -95,6 +95,53 @@ fn test_deployed_contract_has_correct_minter() {
     assert_eq!(nft_client.get_minter(), minter);
 }
 
+/// Test: Deployed contract has the requested per-wallet ticket limit
+#[test]
+fn test_deployed_contract_has_ticket_limit() {
+    let (env, _admin, client, _wasm_hash) = setup_test();
+
+    let minter = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+
+    let deployed_address = client.deploy_ticket(&minter, &salt, &4u32, &100u128, &TransferPolicy::Free);
+
+    let nft_client = ticket_nft_contract::Client::new(&env, &deployed_address);
+    assert_eq!(nft_client.max_tickets_per_wallet(), 4);
+}
+
+/// Test: Deployed contract has the requested max supply
+#[test]
+fn test_deployed_contract_has_max_supply() {
+    let (env, _admin, client, _wasm_hash) = setup_test();
+
+    let minter = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[8u8; 32]);
+
+    let deployed_address = client.deploy_ticket(&minter, &salt, &1u32, &250u128, &TransferPolicy::Free);
+
+    let nft_client = ticket_nft_contract::Client::new(&env, &deployed_address);
+    assert_eq!(nft_client.max_supply(), 250);
+    assert_eq!(nft_client.total_supply(), 0);
+}
+
+/// Test: Deployed contract has the requested transfer policy
+#[test]
+fn test_deployed_contract_has_transfer_policy() {
+    let (env, _admin, client, _wasm_hash) = setup_test();
+
+    let minter = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[9u8; 32]);
+
+    let deployed_address =
+        client.deploy_ticket(&minter, &salt, &1u32, &100u128, &TransferPolicy::Soulbound);
+
+    let nft_client = ticket_nft_contract::Client::new(&env, &deployed_address);
+    assert_eq!(
+        nft_client.transfer_policy(),
+        ticket_nft_contract::TransferPolicy::Soulbound
+    );
+}
+
 /// Test: Can deploy multiple contracts with different salts
 #[test]
 fn test_can_deploy_multiple_contracts() {
@@ -109,9 +156,9 @@ fn test_can_deploy_multiple_contracts() {
     let salt3 = BytesN::from_array(&env, &[6u8; 32]);
 
     // Deploy three ticket contracts
-    let addr1 = client.deploy_ticket(&minter1, &salt1);
-    let addr2 = client.deploy_ticket(&minter2, &salt2);
-    let addr3 = client.deploy_ticket(&minter3, &salt3);
+    let addr1 = client.deploy_ticket(&minter1, &salt1, &1u32, &100u128, &TransferPolicy::Free);
+    let addr2 = client.deploy_ticket(&minter2, &salt2, &1u32, &100u128, &TransferPolicy::Free);
+    let addr3 = client.deploy_ticket(&minter3, &salt3, &1u32, &100u128, &TransferPolicy::Free);
 
     // Verify all addresses are different
     assert_ne!(addr1, addr2);
@@ -139,7 +186,7 @@ fn test_tracking_storage_works() {
     for i in 1u8..=5u8 {
         let minter = Address::generate(&env);
         let salt = BytesN::from_array(&env, &[i + 10; 32]);
-        client.deploy_ticket(&minter, &salt);
+        client.deploy_ticket(&minter, &salt, &1u32, &100u128, &TransferPolicy::Free);
         assert_eq!(client.get_total_tickets(), i as u32);
     }
 
@@ -156,7 +203,7 @@ fn test_admin_authorization_required() {
     let salt = BytesN::from_array(&env, &[20u8; 32]);
 
     // Deploy ticket (this should require admin auth)
-    client.deploy_ticket(&minter, &salt);
+    client.deploy_ticket(&minter, &salt, &1u32, &100u128, &TransferPolicy::Free);
 
     // Verify admin was the authorized party
     let auths = env.auths();
@@ -4,7 +4,9 @@
 
 #![no_std]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, Env, String, Symbol,
+};
 
 // Error handling
 #[contracterror]
@@ -14,6 +16,27 @@ pub enum Error {
     InvalidTokenId = 2,
     Unauthorized = 3,
     RecipientAlreadyHasTicket = 4,
+    InvalidTicketLimit = 5,
+    MetadataTooLong = 6,
+    MaxSupplyReached = 7,
+    InvalidMaxSupply = 8,
+    TicketCheckedIn = 9,
+    TransferNotAllowed = 10,
+    TransferLocked = 11,
+    InvalidTransferLock = 12,
+    SelfTransfer = 13,
+}
+
+/// Rules for moving tickets between wallets
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    /// Holders can transfer tickets freely
+    Free,
+    /// Tickets can never leave the wallet they were minted to
+    Soulbound,
+    /// Each transfer must be approved by the minter
+    OrganizerApproved,
 }
 
 /// Storage keys for the NFT contract
@@ -28,25 +51,83 @@ pub enum DataKey {
     Owner(u128),
     /// Balance: owner -> count
     Balance(Address),
+    /// Ticket tier: token_id -> tier_id
+    Tier(u128),
+    /// Maximum number of tickets a single wallet can hold
+    MaxTicketsPerWallet,
+    /// Collection name
+    Name,
+    /// Collection symbol
+    Symbol,
+    /// Base URI for token metadata
+    BaseUri,
+    /// Approved spender for a single token: token_id -> spender
+    Approved(u128),
+    /// Operator approval: (owner, operator) -> bool
+    ApprovedForAll(Address, Address),
+    /// Maximum number of tickets in circulation
+    MaxSupply,
+    /// Number of tickets currently in circulation (minted and not burned)
+    TotalSupply,
+    /// Check-in timestamp: token_id -> u64
+    CheckedIn(u128),
+    /// Transfer policy of the collection
+    TransferPolicy,
+    /// Recipient the minter approved a transfer to: token_id -> recipient
+    TransferApproval(u128),
+    /// Period during which transfers are frozen: (locked_from, locked_until)
+    TransferLock,
 }
 
+/// Maximum length of a generated token URI
+const MAX_URI_LENGTH: usize = 256;
+
 /// Ticket NFT Contract
 ///
 /// Minimal NFT implementation for event tickets.
-/// Each user can only hold one ticket per event.
+/// Each wallet can hold up to a configured number of tickets per event.
 #[contract]
 pub struct TicketNft;
 
 #[contractimpl]
 impl TicketNft {
-    /// Initialize the NFT contract with a minter address
+    /// Initialize the NFT contract with a minter address, per-wallet ticket limit and max supply
     ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `minter` - Address that can mint new tickets
-    pub fn __constructor(env: Env, minter: Address) {
+    /// * `max_tickets_per_wallet` - Maximum tickets a single wallet can hold (1 for one-per-user)
+    /// * `max_supply` - Maximum number of tickets in circulation
+    /// * `transfer_policy` - Whether and how tickets can be transferred
+    ///
+    /// # Panics
+    /// - If `max_tickets_per_wallet` or `max_supply` is 0
+    pub fn __constructor(
+        env: Env,
+        minter: Address,
+        max_tickets_per_wallet: u32,
+        max_supply: u128,
+        transfer_policy: TransferPolicy,
+    ) {
+        if max_tickets_per_wallet == 0 {
+            panic!("Ticket limit must be greater than 0");
+        }
+        if max_supply == 0 {
+            panic!("Max supply must be greater than 0");
+        }
+
         env.storage().instance().set(&DataKey::Minter, &minter);
         env.storage().instance().set(&DataKey::NextTokenId, &1u128);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxTicketsPerWallet, &max_tickets_per_wallet);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxSupply, &max_supply);
+        env.storage().instance().set(&DataKey::TotalSupply, &0u128);
+        env.storage()
+            .instance()
+            .set(&DataKey::TransferPolicy, &transfer_policy);
 
         // Extend instance TTL
         env.storage()
@@ -59,26 +140,30 @@ impl TicketNft {
     /// # Arguments
     /// * `env` - The contract environment
     /// * `recipient` - Address to receive the ticket
+    /// * `tier_id` - Ticket tier the token was sold under
     ///
     /// # Returns
     /// The token ID of the minted ticket
     ///
     /// # Errors
     /// - If caller is not the minter
-    /// - If recipient already has a ticket
-    pub fn mint_ticket_nft(env: Env, recipient: Address) -> Result<u128, Error> {
+    /// - If the max supply has been reached
+    /// - If recipient already holds the maximum number of tickets
+    pub fn mint_ticket_nft(env: Env, recipient: Address, tier_id: u32) -> Result<u128, Error> {
         // Authorize: only minter can mint
         let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
         minter.require_auth();
 
-        // Check if user already has a ticket (one per user)
-        let current_balance: u128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Balance(recipient.clone()))
-            .unwrap_or(0);
+        // Check the supply cap
+        let total_supply = Self::total_supply(env.clone());
+        if total_supply >= Self::max_supply(env.clone()) {
+            return Err(Error::MaxSupplyReached);
+        }
+
+        // Check if user already holds the maximum number of tickets
+        let current_balance = Self::balance_of(env.clone(), recipient.clone());
 
-        if current_balance > 0 {
+        if current_balance >= Self::max_tickets_per_wallet(env.clone()) as u128 {
             return Err(Error::UserAlreadyHasTicket);
         }
 
@@ -102,11 +187,22 @@ impl TicketNft {
 
         env.storage()
             .persistent()
-            .set(&DataKey::Balance(recipient.clone()), &1u128);
+            .set(&DataKey::Tier(token_id), &tier_id);
+
+        // Extend persistent TTL for tier
+        env.storage().persistent().extend_ttl(
+            &DataKey::Tier(token_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(recipient.clone()), &(current_balance + 1));
 
         // Extend persistent TTL for balance
         env.storage().persistent().extend_ttl(
-            &DataKey::Balance(recipient),
+            &DataKey::Balance(recipient.clone()),
             30 * 24 * 60 * 60 / 5,
             100 * 24 * 60 * 60 / 5,
         );
@@ -114,12 +210,18 @@ impl TicketNft {
         env.storage()
             .instance()
             .set(&DataKey::NextTokenId, &(token_id + 1));
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &(total_supply + 1));
 
         // Extend instance TTL on update
         env.storage()
             .instance()
             .extend_ttl(30 * 24 * 60 * 60 / 5, 100 * 24 * 60 * 60 / 5);
 
+        env.events()
+            .publish((Symbol::new(&env, "mint"), recipient, token_id), tier_id);
+
         Ok(token_id)
     }
 
@@ -135,6 +237,18 @@ impl TicketNft {
             .ok_or(Error::InvalidTokenId)
     }
 
+    /// Get the tier a token was sold under
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to query
+    pub fn tier_of(env: Env, token_id: u128) -> Result<u32, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tier(token_id))
+            .ok_or(Error::InvalidTokenId)
+    }
+
     /// Get the balance of an owner
     ///
     /// # Arguments
@@ -147,9 +261,9 @@ impl TicketNft {
             .unwrap_or(0)
     }
 
-    /// Transfer a ticket NFT from one address to another
+    /// Transfer a ticket NFT from its owner to another address
     ///
-    /// Enforces the one-ticket-per-user rule for the recipient.
+    /// Enforces the per-wallet ticket limit for the recipient.
     ///
     /// # Arguments
     /// * `env` - The contract environment
@@ -159,59 +273,278 @@ impl TicketNft {
     ///
     /// # Errors
     /// - If `from` is not the owner
-    /// - If `to` already has a ticket
-    pub fn transfer_from(env: Env, from: Address, to: Address, token_id: u128) -> Result<(), Error> {
+    /// - If `to` already holds the maximum number of tickets
+    /// - If the ticket has been checked in
+    /// - If the transfer policy doesn't allow the transfer
+    /// - If transfers are frozen
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u128) -> Result<(), Error> {
         from.require_auth();
 
-        if !Self::is_valid(env.clone(), token_id) {
-            return Err(Error::InvalidTokenId);
+        Self::do_transfer(&env, &from, &to, token_id)
+    }
+
+    /// Transfer a ticket NFT on behalf of its owner
+    ///
+    /// The spender must be the owner, the approved address for the token,
+    /// or an operator approved for all of the owner's tokens.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `spender` - Address performing the transfer
+    /// * `from` - Current owner of the ticket
+    /// * `to` - Recipient address
+    /// * `token_id` - The token ID to transfer
+    ///
+    /// # Errors
+    /// - If `from` is not the owner
+    /// - If `spender` is not allowed to transfer the token
+    /// - If `to` already holds the maximum number of tickets
+    /// - If the ticket has been checked in
+    /// - If the transfer policy doesn't allow the transfer
+    /// - If transfers are frozen
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u128,
+    ) -> Result<(), Error> {
+        spender.require_auth();
+
+        if spender != from
+            && Self::get_approved(env.clone(), token_id) != Some(spender.clone())
+            && !Self::is_approved_for_all(env.clone(), from.clone(), spender)
+        {
+            return Err(Error::Unauthorized);
         }
 
+        Self::do_transfer(&env, &from, &to, token_id)
+    }
+
+    /// Approve an address to transfer a single token
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `approver` - The token owner or one of its operators
+    /// * `approved` - Address to approve, or `None` to clear the approval
+    /// * `token_id` - The token ID to approve
+    ///
+    /// # Errors
+    /// - If the token does not exist
+    /// - If `approver` is neither the owner nor an operator of the owner
+    pub fn approve(
+        env: Env,
+        approver: Address,
+        approved: Option<Address>,
+        token_id: u128,
+    ) -> Result<(), Error> {
+        approver.require_auth();
+
         let owner = Self::owner_of(env.clone(), token_id)?;
-        if owner != from {
+        if approver != owner
+            && !Self::is_approved_for_all(env.clone(), owner.clone(), approver.clone())
+        {
             return Err(Error::Unauthorized);
         }
 
-        if Self::balance_of(env.clone(), to.clone()) > 0 {
-            return Err(Error::RecipientAlreadyHasTicket);
+        match &approved {
+            Some(spender) => {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Approved(token_id), spender);
+                env.storage().persistent().extend_ttl(
+                    &DataKey::Approved(token_id),
+                    30 * 24 * 60 * 60 / 5,
+                    100 * 24 * 60 * 60 / 5,
+                );
+            }
+            None => env
+                .storage()
+                .persistent()
+                .remove(&DataKey::Approved(token_id)),
         }
 
-        // Update ownership
+        env.events()
+            .publish((Symbol::new(&env, "approve"), owner, token_id), approved);
+
+        Ok(())
+    }
+
+    /// Get the address approved to transfer a token, if any
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to query
+    pub fn get_approved(env: Env, token_id: u128) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Approved(token_id))
+    }
+
+    /// Approve or revoke an operator for all of the owner's tokens
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The token owner
+    /// * `operator` - Address to approve or revoke
+    /// * `approved` - Whether the operator is approved
+    pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+
+        let key = DataKey::ApprovedForAll(owner.clone(), operator.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(
+                &key,
+                30 * 24 * 60 * 60 / 5,
+                100 * 24 * 60 * 60 / 5,
+            );
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "approve_for_all"), owner),
+            (operator, approved),
+        );
+    }
+
+    /// Check whether an operator is approved for all of an owner's tokens
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The token owner
+    /// * `operator` - The operator to check
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
         env.storage()
             .persistent()
-            .set(&DataKey::Owner(token_id), &to);
+            .get(&DataKey::ApprovedForAll(owner, operator))
+            .unwrap_or(false)
+    }
 
-        // Update balances
+    /// Get the collection name
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn name(env: Env) -> String {
         env.storage()
-            .persistent()
-            .set(&DataKey::Balance(from), &0u128);
+            .instance()
+            .get(&DataKey::Name)
+            .unwrap_or_else(|| String::from_str(&env, ""))
+    }
+
+    /// Get the collection symbol
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn symbol(env: Env) -> String {
         env.storage()
-            .persistent()
-            .set(&DataKey::Balance(to), &1u128);
-        
-        Ok(())
+            .instance()
+            .get(&DataKey::Symbol)
+            .unwrap_or_else(|| String::from_str(&env, ""))
     }
 
-    /// Burn a ticket NFT, removing it from existence
+    /// Get the metadata URI of a token: the base URI followed by the token ID
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to query
+    ///
+    /// # Errors
+    /// - If the token does not exist
+    /// - If the resulting URI is longer than 256 bytes
+    pub fn token_uri(env: Env, token_id: u128) -> Result<String, Error> {
+        if !Self::is_valid(env.clone(), token_id) {
+            return Err(Error::InvalidTokenId);
+        }
+
+        let base_uri: String = env
+            .storage()
+            .instance()
+            .get(&DataKey::BaseUri)
+            .unwrap_or_else(|| String::from_str(&env, ""));
+
+        // u128::MAX has 39 decimal digits
+        let base_len = base_uri.len() as usize;
+        if base_len + 39 > MAX_URI_LENGTH {
+            return Err(Error::MetadataTooLong);
+        }
+
+        let mut buf = [0u8; MAX_URI_LENGTH];
+        base_uri.copy_into_slice(&mut buf[..base_len]);
+
+        let mut digits = [0u8; 39];
+        let mut digit_count = 0;
+        let mut remaining = token_id;
+        loop {
+            digits[digit_count] = b'0' + (remaining % 10) as u8;
+            digit_count += 1;
+            remaining /= 10;
+            if remaining == 0 {
+                break;
+            }
+        }
+        for i in 0..digit_count {
+            buf[base_len + i] = digits[digit_count - 1 - i];
+        }
+
+        Ok(String::from_bytes(&env, &buf[..base_len + digit_count]))
+    }
+
+    /// Set the collection metadata
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `name` - Collection name
+    /// * `symbol` - Collection symbol
+    /// * `base_uri` - Prefix of every token URI
+    ///
+    /// # Authorization
+    /// Requires minter authorization
+    pub fn set_metadata(env: Env, name: String, symbol: String, base_uri: String) {
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
+
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+        env.storage().instance().set(&DataKey::BaseUri, &base_uri);
+    }
+
+    /// Burn a ticket NFT, removing it from existence. Holders burn through the minter
+    /// so it can free the ticket's seat.
     ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `token_id` - The token ID to burn
     ///
-    /// # Panics
-    /// - If caller is not the token owner
+    /// # Authorization
+    /// Requires both owner and minter authorization
     pub fn burn(env: Env, token_id: u128) {
         let owner = Self::owner_of(env.clone(), token_id).expect("Invalid token id");
 
-        // Authorize: only owner can burn
-        // In a real implementation, we might want to allow minter too,
-        // but require_auth() is the most reliable way to handle this in Soroban.
         owner.require_auth();
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
 
         env.storage().persistent().remove(&DataKey::Owner(token_id));
+        env.storage().persistent().remove(&DataKey::Tier(token_id));
         env.storage()
             .persistent()
-            .set(&DataKey::Balance(owner), &0u128);
+            .remove(&DataKey::Approved(token_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TransferApproval(token_id));
+
+        let balance = Self::balance_of(env.clone(), owner.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(owner.clone()), &(balance - 1));
+
+        let total_supply = Self::total_supply(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &(total_supply - 1));
+
+        env.events()
+            .publish((Symbol::new(&env, "burn"), owner, token_id), ());
     }
 
     /// Check if a token is valid (exists and not burned)
@@ -223,6 +556,286 @@ impl TicketNft {
         env.storage().persistent().has(&DataKey::Owner(token_id))
     }
 
+    /// Mark a ticket as redeemed at the current ledger timestamp
+    ///
+    /// Redeemed tickets can no longer be transferred.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to check in
+    ///
+    /// # Errors
+    /// - If the token doesn't exist
+    /// - If the token was already checked in
+    ///
+    /// # Authorization
+    /// Requires minter authorization
+    pub fn check_in(env: Env, token_id: u128) -> Result<u64, Error> {
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
+
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if Self::is_checked_in(env.clone(), token_id) {
+            return Err(Error::TicketCheckedIn);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::CheckedIn(token_id), &timestamp);
+        env.storage().persistent().extend_ttl(
+            &DataKey::CheckedIn(token_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "checked_in"), owner, token_id),
+            timestamp,
+        );
+
+        Ok(timestamp)
+    }
+
+    /// Check if a ticket has been redeemed
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to query
+    pub fn is_checked_in(env: Env, token_id: u128) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::CheckedIn(token_id))
+    }
+
+    /// Get the time a ticket was redeemed, if it has been
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to query
+    pub fn checked_in_at(env: Env, token_id: u128) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CheckedIn(token_id))
+    }
+
+    /// Get the transfer policy of the collection
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn transfer_policy(env: Env) -> TransferPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::TransferPolicy)
+            .unwrap_or(TransferPolicy::Free)
+    }
+
+    /// Approve a single transfer of a ticket to a recipient
+    ///
+    /// Used by collections with the organizer-approved transfer policy. The approval
+    /// is consumed by the next transfer to `to`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to approve a transfer for
+    /// * `to` - Recipient the ticket may be transferred to
+    ///
+    /// # Errors
+    /// - If the token doesn't exist
+    ///
+    /// # Authorization
+    /// Requires minter authorization
+    pub fn approve_transfer(env: Env, token_id: u128, to: Address) -> Result<(), Error> {
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
+
+        let owner = Self::owner_of(env.clone(), token_id)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TransferApproval(token_id), &to);
+        env.storage().persistent().extend_ttl(
+            &DataKey::TransferApproval(token_id),
+            30 * 24 * 60 * 60 / 5,
+            100 * 24 * 60 * 60 / 5,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "transfer_approved"), owner, token_id),
+            to,
+        );
+
+        Ok(())
+    }
+
+    /// Get the recipient the minter approved a ticket's transfer to, if any
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token_id` - The token ID to query
+    pub fn get_transfer_approval(env: Env, token_id: u128) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TransferApproval(token_id))
+    }
+
+    /// Freeze transfers between two timestamps (inclusive)
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `locked_from` - Time transfers start being blocked
+    /// * `locked_until` - Time after which transfers are allowed again
+    ///
+    /// # Errors
+    /// - If `locked_from` is after `locked_until`
+    ///
+    /// # Authorization
+    /// Requires minter authorization
+    pub fn set_transfer_lock(env: Env, locked_from: u64, locked_until: u64) -> Result<(), Error> {
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
+
+        if locked_from > locked_until {
+            return Err(Error::InvalidTransferLock);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TransferLock, &(locked_from, locked_until));
+
+        env.events().publish(
+            (Symbol::new(&env, "transfer_lock_set"),),
+            (locked_from, locked_until),
+        );
+
+        Ok(())
+    }
+
+    /// Remove the transfer freeze period
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Authorization
+    /// Requires minter authorization
+    pub fn clear_transfer_lock(env: Env) {
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
+
+        env.storage().instance().remove(&DataKey::TransferLock);
+
+        env.events()
+            .publish((Symbol::new(&env, "transfer_lock_cleared"),), ());
+    }
+
+    /// Get the transfer freeze period as `(locked_from, locked_until)`, if any
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn transfer_lock(env: Env) -> Option<(u64, u64)> {
+        env.storage().instance().get(&DataKey::TransferLock)
+    }
+
+    /// Check if transfers are currently frozen
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn is_transfer_locked(env: Env) -> bool {
+        let now = env.ledger().timestamp();
+        Self::transfer_lock(env)
+            .is_some_and(|(locked_from, locked_until)| locked_from <= now && now <= locked_until)
+    }
+
+    /// Get the maximum number of tickets a single wallet can hold
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn max_tickets_per_wallet(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxTicketsPerWallet)
+            .unwrap_or(1)
+    }
+
+    /// Set the maximum number of tickets a single wallet can hold
+    ///
+    /// Wallets already above a lowered limit keep their tickets but cannot receive more.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `limit` - New per-wallet limit
+    ///
+    /// # Errors
+    /// - If `limit` is 0
+    ///
+    /// # Authorization
+    /// Requires minter authorization
+    pub fn set_max_tickets_per_wallet(env: Env, limit: u32) -> Result<(), Error> {
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
+
+        if limit == 0 {
+            return Err(Error::InvalidTicketLimit);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxTicketsPerWallet, &limit);
+
+        Ok(())
+    }
+
+    /// Get the number of tickets currently in circulation (minted and not burned)
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn total_supply(env: Env) -> u128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    /// Get the maximum number of tickets in circulation
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn max_supply(env: Env) -> u128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxSupply)
+            .unwrap_or(0)
+    }
+
+    /// Set the maximum number of tickets in circulation
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `max_supply` - New supply cap
+    ///
+    /// # Errors
+    /// - If `max_supply` is below the number of tickets in circulation, or 0
+    ///
+    /// # Authorization
+    /// Requires minter authorization
+    pub fn set_max_supply(env: Env, max_supply: u128) -> Result<(), Error> {
+        let minter: Address = env.storage().instance().get(&DataKey::Minter).unwrap();
+        minter.require_auth();
+
+        if max_supply == 0 || max_supply < Self::total_supply(env.clone()) {
+            return Err(Error::InvalidMaxSupply);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxSupply, &max_supply);
+
+        env.events()
+            .publish((Symbol::new(&env, "max_supply_updated"),), max_supply);
+
+        Ok(())
+    }
+
     /// Get the minter address
     ///
     /// # Arguments
@@ -233,6 +846,78 @@ impl TicketNft {
             .get(&DataKey::Minter)
             .expect("Not initialized")
     }
+
+    fn do_transfer(env: &Env, from: &Address, to: &Address, token_id: u128) -> Result<(), Error> {
+        if !Self::is_valid(env.clone(), token_id) {
+            return Err(Error::InvalidTokenId);
+        }
+
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if owner != *from {
+            return Err(Error::Unauthorized);
+        }
+
+        // Balances are read and written per wallet, so a self-transfer would inflate them
+        if from == to {
+            return Err(Error::SelfTransfer);
+        }
+
+        if Self::is_checked_in(env.clone(), token_id) {
+            return Err(Error::TicketCheckedIn);
+        }
+
+        if Self::is_transfer_locked(env.clone()) {
+            return Err(Error::TransferLocked);
+        }
+
+        match Self::transfer_policy(env.clone()) {
+            TransferPolicy::Free => {}
+            TransferPolicy::Soulbound => return Err(Error::TransferNotAllowed),
+            TransferPolicy::OrganizerApproved => {
+                if Self::get_transfer_approval(env.clone(), token_id).as_ref() != Some(to) {
+                    return Err(Error::TransferNotAllowed);
+                }
+            }
+        }
+
+        let from_balance = Self::balance_of(env.clone(), from.clone());
+        let to_balance = Self::balance_of(env.clone(), to.clone());
+
+        if to_balance >= Self::max_tickets_per_wallet(env.clone()) as u128 {
+            return Err(Error::RecipientAlreadyHasTicket);
+        }
+
+        // Update ownership and clear the single-token and transfer approvals
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owner(token_id), to);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Approved(token_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TransferApproval(token_id));
+
+        // Update balances
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(from.clone()), &(from_balance - 1));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to.clone()), &(to_balance + 1));
+
+        env.events().publish(
+            (
+                Symbol::new(env, "transfer"),
+                from.clone(),
+                to.clone(),
+                token_id,
+            ),
+            (),
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,9 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::testutils::Address as _;
+use soroban_sdk::testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke};
 use soroban_sdk::Env;
+use soroban_sdk::{vec, IntoVal};
 
 #[test]
 fn test_minting() {
@@ -13,19 +14,19 @@ fn test_minting() {
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
 
-    let contract_id = env.register(TicketNft, (&minter,));
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
     let client = TicketNftClient::new(&env, &contract_id);
 
     // Mint first ticket
-    let token_id1 = client.mint_ticket_nft(&user1).unwrap();
+    let token_id1 = client.mint_ticket_nft(&user1, &0);
     assert_eq!(token_id1, 1);
-    assert_eq!(client.owner_of(&token_id1).unwrap(), user1);
+    assert_eq!(client.owner_of(&token_id1), user1);
     assert_eq!(client.balance_of(&user1), 1);
 
     // Mint second ticket
-    let token_id2 = client.mint_ticket_nft(&user2).unwrap();
+    let token_id2 = client.mint_ticket_nft(&user2, &0);
     assert_eq!(token_id2, 2);
-    assert_eq!(client.owner_of(&token_id2).unwrap(), user2);
+    assert_eq!(client.owner_of(&token_id2), user2);
     assert_eq!(client.balance_of(&user2), 1);
 }
 
@@ -37,13 +38,12 @@ fn test_cannot_mint_twice_to_same_user() {
     let minter = Address::generate(&env);
     let user = Address::generate(&env);
 
-    let contract_id = env.register(TicketNft, (&minter,));
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
     let client = TicketNftClient::new(&env, &contract_id);
 
-    client.mint_ticket_nft(&user).unwrap();
-    let result = client.mint_ticket_nft(&user);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::UserAlreadyHasTicket);
+    client.mint_ticket_nft(&user, &0);
+    let result = client.try_mint_ticket_nft(&user, &0);
+    assert_eq!(result, Err(Ok(Error::UserAlreadyHasTicket)));
 }
 
 #[test]
@@ -55,18 +55,43 @@ fn test_transfer() {
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
 
-    let contract_id = env.register(TicketNft, (&minter,));
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
     let client = TicketNftClient::new(&env, &contract_id);
 
-    let token_id = client.mint_ticket_nft(&user1).unwrap();
+    let token_id = client.mint_ticket_nft(&user1, &0);
 
-    client.transfer_from(&user1, &user2, &token_id).unwrap();
+    client.transfer(&user1, &user2, &token_id);
 
-    assert_eq!(client.owner_of(&token_id).unwrap(), user2);
+    assert_eq!(client.owner_of(&token_id), user2);
     assert_eq!(client.balance_of(&user1), 0);
     assert_eq!(client.balance_of(&user2), 1);
 }
 
+#[test]
+fn test_cannot_transfer_to_self() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &3u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&user, &0);
+
+    let result = client.try_transfer(&user, &user, &token_id);
+    assert_eq!(result, Err(Ok(Error::SelfTransfer)));
+    let result = client.try_transfer_from(&user, &user, &user, &token_id);
+    assert_eq!(result, Err(Ok(Error::SelfTransfer)));
+
+    // The wallet still holds exactly one ticket and can receive the rest of its limit
+    assert_eq!(client.balance_of(&user), 1);
+    client.mint_ticket_nft(&user, &0);
+    client.mint_ticket_nft(&user, &0);
+    assert_eq!(client.balance_of(&user), 3);
+}
+
 #[test]
 fn test_cannot_transfer_to_user_with_ticket() {
     let env = Env::default();
@@ -76,15 +101,14 @@ fn test_cannot_transfer_to_user_with_ticket() {
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
 
-    let contract_id = env.register(TicketNft, (&minter,));
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
     let client = TicketNftClient::new(&env, &contract_id);
 
-    let token_id1 = client.mint_ticket_nft(&user1).unwrap();
-    let _token_id2 = client.mint_ticket_nft(&user2).unwrap();
+    let token_id1 = client.mint_ticket_nft(&user1, &0);
+    let _token_id2 = client.mint_ticket_nft(&user2, &0);
 
-    let result = client.transfer_from(&user1, &user2, &token_id1);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::RecipientAlreadyHasTicket);
+    let result = client.try_transfer(&user1, &user2, &token_id1);
+    assert_eq!(result, Err(Ok(Error::RecipientAlreadyHasTicket)));
 }
 
 #[test]
@@ -96,11 +120,11 @@ fn test_only_minter_can_mint() {
     let minter = Address::generate(&env);
     let user = Address::generate(&env);
 
-    let contract_id = env.register(TicketNft, (&minter,));
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
     let client = TicketNftClient::new(&env, &contract_id);
 
     // Without mock_all_auths, require_auth() will fail for the minter
-    let _ = client.mint_ticket_nft(&user);
+    let _ = client.mint_ticket_nft(&user, &0);
 }
 
 #[test]
@@ -111,10 +135,10 @@ fn test_burn() {
     let minter = Address::generate(&env);
     let user = Address::generate(&env);
 
-    let contract_id = env.register(TicketNft, (&minter,));
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
     let client = TicketNftClient::new(&env, &contract_id);
 
-    let token_id = client.mint_ticket_nft(&user).unwrap();
+    let token_id = client.mint_ticket_nft(&user, &0);
     assert!(client.is_valid(&token_id));
 
     client.burn(&token_id);
@@ -122,6 +146,33 @@ fn test_burn() {
     assert_eq!(client.balance_of(&user), 0);
 }
 
+#[test]
+fn test_holder_cannot_burn_without_minter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&user, &0);
+
+    // Only the holder signs, so the minter never gets to free the seat
+    env.mock_auths(&[MockAuth {
+        address: &user,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "burn",
+            args: (token_id,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_burn(&token_id).is_err());
+    assert!(client.is_valid(&token_id));
+}
+
 #[test]
 fn test_cannot_transfer_burned_token() {
     let env = Env::default();
@@ -131,12 +182,516 @@ fn test_cannot_transfer_burned_token() {
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
 
-    let contract_id = env.register(TicketNft, (&minter,));
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&user1, &0);
+    client.burn(&token_id);
+
+    let result = client.try_transfer(&user1, &user2, &token_id);
+    assert_eq!(result, Err(Ok(Error::InvalidTokenId)));
+}
+
+#[test]
+fn test_mint_records_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let general = client.mint_ticket_nft(&user1, &0);
+    let vip = client.mint_ticket_nft(&user2, &2);
+
+    assert_eq!(client.tier_of(&general), 0);
+    assert_eq!(client.tier_of(&vip), 2);
+
+    client.burn(&vip);
+    assert_eq!(client.try_tier_of(&vip), Err(Ok(Error::InvalidTokenId)));
+}
+
+#[test]
+fn test_wallet_limit_allows_multiple_tickets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &3u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+    assert_eq!(client.max_tickets_per_wallet(), 3);
+
+    let token_id1 = client.mint_ticket_nft(&user1, &0);
+    client.mint_ticket_nft(&user1, &0);
+    client.mint_ticket_nft(&user1, &0);
+    assert_eq!(client.balance_of(&user1), 3);
+
+    let result = client.try_mint_ticket_nft(&user1, &0);
+    assert_eq!(result, Err(Ok(Error::UserAlreadyHasTicket)));
+
+    client.transfer(&user1, &user2, &token_id1);
+    assert_eq!(client.balance_of(&user1), 2);
+    assert_eq!(client.balance_of(&user2), 1);
+
+    client.burn(&token_id1);
+    assert_eq!(client.balance_of(&user2), 0);
+}
+
+#[test]
+fn test_set_max_tickets_per_wallet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    client.mint_ticket_nft(&user, &0);
+    client.set_max_tickets_per_wallet(&2);
+    client.mint_ticket_nft(&user, &0);
+    assert_eq!(client.balance_of(&user), 2);
+
+    let result = client.try_set_max_tickets_per_wallet(&0);
+    assert_eq!(result, Err(Ok(Error::InvalidTicketLimit)));
+}
+
+#[test]
+fn test_approved_spender_can_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&owner, &0);
+    client.approve(&owner, &Some(spender.clone()), &token_id);
+    assert_eq!(client.get_approved(&token_id), Some(spender.clone()));
+
+    client.transfer_from(&spender, &owner, &recipient, &token_id);
+
+    assert_eq!(client.owner_of(&token_id), recipient);
+    // Approval is cleared on transfer
+    assert_eq!(client.get_approved(&token_id), None);
+}
+
+#[test]
+fn test_unapproved_spender_cannot_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&owner, &0);
+
+    let result = client.try_transfer_from(&spender, &owner, &spender, &token_id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    client.approve(&owner, &Some(spender.clone()), &token_id);
+    client.approve(&owner, &None, &token_id);
+    let result = client.try_transfer_from(&spender, &owner, &spender, &token_id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_operator_can_approve_and_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&owner, &0);
+    client.set_approval_for_all(&owner, &operator, &true);
+    assert!(client.is_approved_for_all(&owner, &operator));
+
+    // Operators can approve on the owner's behalf
+    client.approve(&operator, &Some(spender.clone()), &token_id);
+    assert_eq!(client.get_approved(&token_id), Some(spender));
+
+    client.transfer_from(&operator, &owner, &recipient, &token_id);
+    assert_eq!(client.owner_of(&token_id), recipient);
+
+    client.set_approval_for_all(&owner, &operator, &false);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+}
+
+#[test]
+fn test_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    client.set_metadata(
+        &String::from_str(&env, "Rust Conference 2026"),
+        &String::from_str(&env, "TICKET"),
+        &String::from_str(&env, "https://crowdpass.live/tickets/"),
+    );
+    assert_eq!(
+        client.name(),
+        String::from_str(&env, "Rust Conference 2026")
+    );
+    assert_eq!(client.symbol(), String::from_str(&env, "TICKET"));
+
+    let token_id = client.mint_ticket_nft(&user, &0);
+    assert_eq!(
+        client.token_uri(&token_id),
+        String::from_str(&env, "https://crowdpass.live/tickets/1")
+    );
+
+    for _ in 0..11 {
+        client.mint_ticket_nft(&Address::generate(&env), &0);
+    }
+    assert_eq!(
+        client.token_uri(&12),
+        String::from_str(&env, "https://crowdpass.live/tickets/12")
+    );
+
+    let result = client.try_token_uri(&99);
+    assert_eq!(result, Err(Ok(Error::InvalidTokenId)));
+}
+
+#[test]
+fn test_mint_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&user, &2);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "mint"), user.clone(), token_id).into_val(&env),
+                2u32.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_transfer_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
     let client = TicketNftClient::new(&env, &contract_id);
 
-    let token_id = client.mint_ticket_nft(&user1).unwrap();
+    let token_id = client.mint_ticket_nft(&user1, &0);
+    client.transfer(&user1, &user2, &token_id);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (
+                    Symbol::new(&env, "transfer"),
+                    user1.clone(),
+                    user2.clone(),
+                    token_id,
+                )
+                    .into_val(&env),
+                ().into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_transfer_from_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&owner, &0);
+    client.set_approval_for_all(&owner, &operator, &true);
+    client.transfer_from(&operator, &owner, &recipient, &token_id);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (
+                    Symbol::new(&env, "transfer"),
+                    owner.clone(),
+                    recipient.clone(),
+                    token_id,
+                )
+                    .into_val(&env),
+                ().into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_burn_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&user, &0);
     client.burn(&token_id);
 
-    let result = client.transfer_from(&user1, &user2, &token_id);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidTokenId);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "burn"), user.clone(), token_id).into_val(&env),
+                ().into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_max_supply_enforced() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &2u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+    assert_eq!(client.max_supply(), 2);
+
+    client.mint_ticket_nft(&Address::generate(&env), &0);
+    let token_id = client.mint_ticket_nft(&Address::generate(&env), &0);
+    assert_eq!(client.total_supply(), 2);
+
+    let result = client.try_mint_ticket_nft(&Address::generate(&env), &0);
+    assert_eq!(result, Err(Ok(Error::MaxSupplyReached)));
+
+    // Burning frees up supply
+    client.burn(&token_id);
+    assert_eq!(client.total_supply(), 1);
+    client.mint_ticket_nft(&Address::generate(&env), &0);
+    assert_eq!(client.total_supply(), 2);
+}
+
+#[test]
+fn test_set_max_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &2u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    client.mint_ticket_nft(&Address::generate(&env), &0);
+    client.mint_ticket_nft(&Address::generate(&env), &0);
+
+    client.set_max_supply(&3);
+    assert_eq!(client.max_supply(), 3);
+    client.mint_ticket_nft(&Address::generate(&env), &0);
+
+    let result = client.try_set_max_supply(&2);
+    assert_eq!(result, Err(Ok(Error::InvalidMaxSupply)));
+    assert_eq!(client.max_supply(), 3);
+}
+
+#[test]
+fn test_max_supply_ignores_burned_tickets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &3u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    client.mint_ticket_nft(&Address::generate(&env), &0);
+    client.mint_ticket_nft(&Address::generate(&env), &0);
+    let burned = client.mint_ticket_nft(&Address::generate(&env), &0);
+    client.burn(&burned);
+    let reburned = client.mint_ticket_nft(&Address::generate(&env), &0);
+    client.burn(&reburned);
+
+    // Four tickets were minted, but only two are still in circulation
+    assert_eq!(client.total_supply(), 2);
+    client.set_max_supply(&2);
+    assert_eq!(client.max_supply(), 2);
+
+    let result = client.try_set_max_supply(&1);
+    assert_eq!(result, Err(Ok(Error::InvalidMaxSupply)));
+}
+
+#[test]
+fn test_checked_in_ticket_cannot_be_transferred() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&user1, &0);
+    assert!(!client.is_checked_in(&token_id));
+    assert_eq!(client.checked_in_at(&token_id), None);
+
+    let timestamp = client.check_in(&token_id);
+    assert!(client.is_checked_in(&token_id));
+    assert_eq!(client.checked_in_at(&token_id), Some(timestamp));
+
+    let result = client.try_transfer(&user1, &user2, &token_id);
+    assert_eq!(result, Err(Ok(Error::TicketCheckedIn)));
+
+    let result = client.try_check_in(&token_id);
+    assert_eq!(result, Err(Ok(Error::TicketCheckedIn)));
+}
+
+#[test]
+fn test_soulbound_ticket_cannot_be_transferred() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(
+        TicketNft,
+        (&minter, &1u32, &100u128, TransferPolicy::Soulbound),
+    );
+    let client = TicketNftClient::new(&env, &contract_id);
+    assert_eq!(client.transfer_policy(), TransferPolicy::Soulbound);
+
+    let token_id = client.mint_ticket_nft(&user1, &0);
+
+    let result = client.try_transfer(&user1, &user2, &token_id);
+    assert_eq!(result, Err(Ok(Error::TransferNotAllowed)));
+    let result = client.try_transfer_from(&user1, &user1, &user2, &token_id);
+    assert_eq!(result, Err(Ok(Error::TransferNotAllowed)));
+}
+
+#[test]
+fn test_organizer_approved_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+
+    let contract_id = env.register(
+        TicketNft,
+        (&minter, &1u32, &100u128, TransferPolicy::OrganizerApproved),
+    );
+    let client = TicketNftClient::new(&env, &contract_id);
+
+    let token_id = client.mint_ticket_nft(&user1, &0);
+
+    let result = client.try_transfer(&user1, &user2, &token_id);
+    assert_eq!(result, Err(Ok(Error::TransferNotAllowed)));
+
+    client.approve_transfer(&token_id, &user2);
+    assert_eq!(client.get_transfer_approval(&token_id), Some(user2.clone()));
+
+    // The approval only covers the approved recipient
+    let result = client.try_transfer(&user1, &user3, &token_id);
+    assert_eq!(result, Err(Ok(Error::TransferNotAllowed)));
+
+    client.transfer(&user1, &user2, &token_id);
+    assert_eq!(client.owner_of(&token_id), user2);
+    assert_eq!(client.get_transfer_approval(&token_id), None);
+
+    // The approval is consumed by the transfer
+    let result = client.try_transfer(&user2, &user3, &token_id);
+    assert_eq!(result, Err(Ok(Error::TransferNotAllowed)));
+}
+
+#[test]
+fn test_transfer_lock_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let minter = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(TicketNft, (&minter, &1u32, &100u128, TransferPolicy::Free));
+    let client = TicketNftClient::new(&env, &contract_id);
+    let token_id = client.mint_ticket_nft(&user1, &0);
+
+    client.set_transfer_lock(&1_000, &2_000);
+    assert_eq!(client.transfer_lock(), Some((1_000, 2_000)));
+
+    env.ledger().set_timestamp(1_000);
+    assert!(client.is_transfer_locked());
+    let result = client.try_transfer(&user1, &user2, &token_id);
+    assert_eq!(result, Err(Ok(Error::TransferLocked)));
+
+    env.ledger().set_timestamp(2_001);
+    assert!(!client.is_transfer_locked());
+    client.transfer(&user1, &user2, &token_id);
+    assert_eq!(client.owner_of(&token_id), user2);
+
+    client.clear_transfer_lock();
+    assert_eq!(client.transfer_lock(), None);
+
+    let result = client.try_set_transfer_lock(&2_000, &1_000);
+    assert_eq!(result, Err(Ok(Error::InvalidTransferLock)));
+}